@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
 
 #[allow(unused_imports)]
 use rayon::iter::ParallelIterator;
@@ -25,6 +25,57 @@ fn benchmark_parse(c: &mut Criterion) {
     });
 }
 
+// only fight boundaries are parsed, every other line is skipped after reading its event name,
+// expected throughput is above 430 MiB/s
+fn benchmark_parse_filtered(c: &mut Criterion) {
+    let data = load_log();
+    let kinds = EventKindSet::from(EventKind::BeginLog) | EventKind::BeginCombat | EventKind::EndCombat;
+
+    let mut group = c.benchmark_group("parse_filtered");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+
+    group.bench_function("fight_boundaries", |b| {
+        b.iter(|| {
+            Event::parse_many_filtered(&data, kinds)
+                .for_each(|r| { black_box(r.unwrap()); })
+        });
+    });
+
+    group.finish();
+}
+
+// loading the same events from the text log and from the cache written from it,
+// throughput of both is measured against size of the text log
+fn benchmark_reload(c: &mut Criterion) {
+    let data = load_log();
+
+    let mut writer = EventCacheWriter::new(Vec::new()).unwrap();
+    for event in Event::parse_many_lenient(&data) {
+        writer.write(&event.unwrap()).unwrap();
+    }
+    let cache = writer.into_inner();
+
+    let mut group = c.benchmark_group("reload");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+
+    group.bench_function("text", |b| {
+        b.iter(|| {
+            Event::parse_many_lenient(&data)
+                .for_each(|r| { black_box(r.unwrap()); })
+        });
+    });
+
+    group.bench_function("cache", |b| {
+        b.iter(|| {
+            Event::parse_cache(&cache)
+                .unwrap()
+                .for_each(|r| { black_box(r.unwrap()); })
+        });
+    });
+
+    group.finish();
+}
+
 fn benchmark_parse_state(c: &mut Criterion) {
     let data = load_log();
 
@@ -40,7 +91,7 @@ fn benchmark_parse_state(c: &mut Criterion) {
     });
 }
 
-fn handle_line<'a>(state: &'a mut State, line: &str) -> (&'a State, Event) {
+fn handle_line<'a, 'b>(state: &'a mut State, line: &'b str) -> (&'a State, Event<'b>) {
     let event = Event::parse(line).unwrap();
     state.handle_event(&event);
 
@@ -50,7 +101,7 @@ fn handle_line<'a>(state: &'a mut State, line: &str) -> (&'a State, Event) {
 criterion_group!{
     name = benches;
     config = Criterion::default().sample_size(10).measurement_time(Duration::from_secs(25));
-    targets = benchmark_parse, benchmark_parse_state
+    targets = benchmark_parse, benchmark_parse_filtered, benchmark_reload, benchmark_parse_state
 }
 
 criterion_main!(benches);
@@ -0,0 +1,108 @@
+use std::io::Write;
+
+use eso_parser::eso_serde::{binary, Error, SerializeError};
+use serde::{Deserialize, Serialize};
+
+use crate::Event;
+
+/// Writes events in compact binary format (see `eso_parser::eso_serde::binary`),
+/// that can be loaded much faster than the text log
+///
+/// writes are small and frequent, so writer should be buffered, eg. with `BufWriter`
+///
+/// # Example usage
+/// ```no_run
+/// # use std::{fs::File, io::BufWriter};
+/// # use eso_lib::{Event, EventCacheWriter};
+/// let data = std::fs::read_to_string("Encounter.log").unwrap();
+/// let mut writer = EventCacheWriter::new(BufWriter::new(File::create("Encounter.bin").unwrap())).unwrap();
+///
+/// for event in Event::parse_many_lenient(&data) {
+///     writer.write(&event.unwrap()).unwrap();
+/// }
+///
+/// writer.flush().unwrap();
+///
+/// let cache = std::fs::read("Encounter.bin").unwrap();
+/// for event in Event::parse_cache(&cache).unwrap() {
+///     let _event = event.unwrap();
+/// }
+/// ```
+pub struct EventCacheWriter<W: Write> {
+    serializer: binary::Serializer<W>,
+}
+
+impl<W: Write> EventCacheWriter<W> {
+    /// create writer, and write header of the cache
+    pub fn new(writer: W) -> Result<Self, SerializeError> {
+        Ok(Self {
+            serializer: binary::Serializer::new(writer)?,
+        })
+    }
+
+    pub fn write(&mut self, event: &Event) -> Result<(), SerializeError> {
+        event.serialize(&mut self.serializer)
+    }
+
+    /// write every event from `events`, stopping at the first error
+    pub fn write_all<'a, 'e: 'a>(&mut self, events: impl IntoIterator<Item = &'a Event<'e>>) -> Result<(), SerializeError> {
+        events.into_iter().try_for_each(|event| self.write(event))
+    }
+
+    pub fn flush(&mut self) -> Result<(), SerializeError> {
+        self.serializer.writer_mut().flush()?;
+
+        Ok(())
+    }
+
+    pub fn get_ref(&self) -> &W {
+        self.serializer.writer()
+    }
+
+    /// get back underlying writer, without flushing it
+    pub fn into_inner(self) -> W {
+        self.serializer.into_inner()
+    }
+}
+
+/// Iterator over events read from data written by `EventCacheWriter`,
+/// strings in returned events are borrowed from the data
+///
+/// you probably want to create it with `Event::parse_cache`
+pub struct EventCacheReader<'a> {
+    deserializer: binary::Deserializer<'a>,
+    // data can't be read past the first error
+    failed: bool,
+}
+
+impl<'a> EventCacheReader<'a> {
+    /// create reader, fails if `data` doesn't start with header of the supported version
+    pub fn new(data: &'a [u8]) -> Result<Self, Error> {
+        Ok(Self {
+            deserializer: binary::Deserializer::new(data)?,
+            failed: false,
+        })
+    }
+}
+
+impl<'a> Iterator for EventCacheReader<'a> {
+    type Item = Result<Event<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.deserializer.is_depleted() {
+            return None;
+        }
+
+        let result = Event::deserialize(&mut self.deserializer);
+        self.failed = result.is_err();
+
+        Some(result)
+    }
+}
+
+impl<'a> Event<'a> {
+    /// read events written by `EventCacheWriter`, strings in returned events are borrowed from `data`
+    pub fn parse_cache(data: &'a [u8]) -> Result<EventCacheReader<'a>, Error> {
+        EventCacheReader::new(data)
+    }
+}
@@ -0,0 +1,252 @@
+use std::io::{BufRead, ErrorKind};
+
+use eso_parser::{eso_serde::Error, UNGUARDED_PADDING};
+
+use crate::{Event, LogSchema};
+
+/// Default maximum amount of bytes taken from the reader at once
+const DEFAULT_CHUNK_SIZE: usize = 1 << 20;
+
+/// Iterator over events read from a `BufRead` source
+/// 
+/// you probably want to create it with `Event::parse_reader`
+pub struct EventReader<R> {
+    reader: R,
+    lines: LineBuffer,
+    chunk_size: usize,
+    eof: bool,
+    lenient: bool,
+    // schema selected by the last `BEGIN_LOG` event
+    schema: LogSchema,
+}
+
+/// Buffer holding log data split at line boundaries
+/// 
+/// always keeps `UNGUARDED_PADDING` bytes of spare capacity past its data,
+/// so every line returned from it can be parsed with `UnguardedDeserializer`
+pub(crate) struct LineBuffer {
+    buf: Vec<u8>,
+    // start of the data that was not returned yet
+    head: usize,
+    // end of the data consisting only of complete lines
+    complete: usize,
+    // end of the data that is known to be a valid UTF-8
+    valid: usize,
+    // offset of the buffer start from the beginning of the source
+    offset: u64,
+    // amount of lines returned so far
+    lines: usize,
+}
+
+/// Position of the line in its source
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LinePosition {
+    // 1-based line number
+    line: usize,
+    offset: u64,
+}
+
+impl<R: BufRead> EventReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_chunk_size(reader, DEFAULT_CHUNK_SIZE)
+    }
+
+    /// create reader, that takes at most `chunk_size` bytes from `reader` at once
+    pub fn with_chunk_size(reader: R, chunk_size: usize) -> Self {
+        Self {
+            reader,
+            lines: LineBuffer::with_capacity(chunk_size.min(DEFAULT_CHUNK_SIZE)),
+            chunk_size: chunk_size.max(1),
+            eof: false,
+            lenient: false,
+            schema: LogSchema::default(),
+        }
+    }
+
+    /// yield events of types unknown to this version of the library
+    /// as `EventType::Unknown`, instead of failing
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
+    /// get back underlying reader, data that was buffered but not parsed yet is lost
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn fill(&mut self) -> Result<(), Error> {
+        let chunk = loop {
+            match self.reader.fill_buf() {
+                Ok(chunk) => break chunk,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.eof = true;
+                    return Err(e.into());
+                },
+            }
+        };
+
+        if chunk.is_empty() {
+            self.eof = true;
+            return Ok(());
+        }
+
+        let len = chunk.len().min(self.chunk_size);
+        self.lines.extend(&chunk[..len]);
+        self.reader.consume(len);
+
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for EventReader<R> {
+    type Item = Result<Event<'static>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (position, line) = match self.lines.next_line() {
+                Some(line) => line,
+                // last line doesn't have to be terminated
+                None if self.eof => self.lines.take_remainder()?,
+                None => {
+                    if let Err(e) = self.fill() {
+                        return Some(Err(e));
+                    }
+
+                    continue;
+                },
+            };
+
+            // SAFETY: `LineBuffer` keeps `UNGUARDED_PADDING` bytes of spare capacity past its data
+            let event = line
+                .and_then(|line| Event::recover_unknown(line, unsafe { Event::parse_unguarded(line, self.schema) }, self.lenient))
+                .map_err(|e| position.attach(e));
+
+            if let Ok(event) = &event {
+                self.schema = self.schema.after(event);
+            }
+
+            // buffer is reused, so events can't borrow from it
+            return Some(event.map(Event::into_owned));
+        }
+    }
+}
+
+impl LineBuffer {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(capacity + UNGUARDED_PADDING),
+            head: 0,
+            complete: 0,
+            valid: 0,
+            offset: 0,
+            lines: 0,
+        }
+    }
+
+    /// append data to the buffer, validating every line it completes
+    pub(crate) fn extend(&mut self, data: &[u8]) {
+        // everything that was not validated yet, was already returned
+        if self.head == self.complete {
+            self.valid = self.complete;
+        }
+
+        // move incomplete line to the front, instead of growing the buffer
+        if self.head != 0 {
+            self.buf.drain(..self.head);
+            self.offset += self.head as u64;
+            self.complete -= self.head;
+            self.valid = self.valid.saturating_sub(self.head);
+            self.head = 0;
+        }
+
+        let old_len = self.buf.len();
+        self.buf.reserve(data.len() + UNGUARDED_PADDING);
+        self.buf.extend_from_slice(data);
+
+        if let Some(last_newline) = data.iter().rposition(|&c| c == b'\n') {
+            let end = old_len + last_newline + 1;
+
+            // validate whole chunk at once, if that fails lines are validated one by one
+            if self.valid == self.complete && std::str::from_utf8(&self.buf[self.complete..end]).is_ok() {
+                self.valid = end;
+            }
+
+            self.complete = end;
+        }
+    }
+
+    /// get next complete line, without its terminator
+    pub(crate) fn next_line(&mut self) -> Option<(LinePosition, Result<&str, Error>)> {
+        let start = self.head;
+        let rest = &self.buf[start..self.complete];
+
+        let end = if self.complete == self.valid {
+            // SAFETY: data up to `self.valid` was validated in `extend`
+            unsafe { std::str::from_utf8_unchecked(rest) }.find('\n')?
+        } else {
+            rest.iter().position(|&c| c == b'\n')?
+        };
+
+        self.head += end + 1;
+        let position = self.advance_position(start);
+
+        let line = &self.buf[start..start + end];
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+        if start + end <= self.valid {
+            // SAFETY: same as above
+            Some((position, Ok(unsafe { std::str::from_utf8_unchecked(line) })))
+        } else {
+            Some((position, std::str::from_utf8(line).map_err(Into::into)))
+        }
+    }
+
+    /// take incomplete line from the end of the buffer
+    pub(crate) fn take_remainder(&mut self) -> Option<(LinePosition, Result<&str, Error>)> {
+        if self.complete == self.buf.len() {
+            return None;
+        }
+
+        let start = self.complete;
+        self.head = self.buf.len();
+        self.complete = self.buf.len();
+        let position = self.advance_position(start);
+
+        let line = &self.buf[start..];
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+        Some((position, std::str::from_utf8(line).map_err(Into::into)))
+    }
+
+    fn advance_position(&mut self, line_start: usize) -> LinePosition {
+        self.lines += 1;
+
+        LinePosition {
+            line: self.lines,
+            offset: self.offset + line_start as u64,
+        }
+    }
+
+    /// discard all buffered data
+    pub(crate) fn clear(&mut self) {
+        self.buf.clear();
+        self.head = 0;
+        self.complete = 0;
+        self.valid = 0;
+        self.offset = 0;
+        self.lines = 0;
+    }
+}
+
+impl LinePosition {
+    /// offset in bytes of the line start
+    pub(crate) fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub(crate) fn attach(self, e: Error) -> Error {
+        e.with_position(self.line, self.offset)
+    }
+}
@@ -0,0 +1,184 @@
+use std::iter;
+
+use eso_parser::{eso_serde::Error, UNGUARDED_PADDING};
+use rayon::prelude::*;
+
+use crate::{log_schema::SchemaMap, Event, EventType};
+
+/// Approximate amount of bytes parsed by a single task
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// Events of the whole log, in their original order
+/// 
+/// Log is split into chunks of consecutive lines, that are parsed on all cores
+/// (uses `rayon` under the hood), and then joined back in order,
+/// so unlike `Event::parse_many_par` result can be passed to `State::handle_events`
+/// 
+/// # Example usage
+/// ```no_run
+/// # use eso_lib::{EventStore, State};
+/// let data = std::fs::read_to_string("Encounter.log").unwrap();
+/// let store = EventStore::parse(&data).unwrap();
+/// 
+/// for session in store.sessions() {
+///     let mut state = State::new();
+///     state.handle_events(session);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EventStore<'a> {
+    events: Vec<Event<'a>>,
+    // indices of `BEGIN_LOG` events
+    session_starts: Vec<usize>,
+}
+
+/// Events parsed from a single chunk
+struct Chunk<'a> {
+    // offset of the chunk from the beginning of the log
+    offset: usize,
+    events: Vec<Event<'a>>,
+    // error that stopped parsing, with its line index and offset relative to the chunk
+    error: Option<(usize, usize, Error)>,
+}
+
+impl<'a> EventStore<'a> {
+    /// parse all events, failing on the first line that can't be parsed
+    pub fn parse<T: AsRef<str> + 'a>(s: &'a T) -> Result<Self, Error> {
+        let s = s.as_ref();
+
+        unsafe { Self::_parse(s, s.len(), false) }
+    }
+
+    /// parse all events, event types unknown to this version of the library
+    /// are stored as `EventType::Unknown` instead of failing
+    pub fn parse_lenient<T: AsRef<str> + 'a>(s: &'a T) -> Result<Self, Error> {
+        let s = s.as_ref();
+
+        unsafe { Self::_parse(s, s.len(), true) }
+    }
+
+    /// parse all events from `String` (parsing from `String` gives opportunity for niche optimization)
+    pub fn parse_string(s: &'a String) -> Result<Self, Error> {
+        unsafe { Self::_parse(s.as_str(), s.capacity(), false) }
+    }
+
+    /// # SAFETY
+    /// Caller must ensure that reading `capacity` bytes from the start of `s` is safe
+    pub(crate) unsafe fn _parse(s: &'a str, capacity: usize, lenient: bool) -> Result<Self, Error> {
+        let end = s.as_ptr().add(capacity.saturating_sub(UNGUARDED_PADDING)) as usize;
+        let chunks = split_chunks(s);
+        let schemas = SchemaMap::scan_chunks(&chunks);
+
+        let chunks: Vec<_> = chunks
+            .into_par_iter()
+            .map(|(offset, chunk)| {
+                let mut schema = schemas.get(offset);
+                let mut events = Vec::new();
+
+                for line in chunk.lines() {
+                    match Event::parse_line(line, end, schema, lenient) {
+                        Ok(event) => {
+                            schema = schema.after(&event);
+                            events.push(event);
+                        },
+                        Err(e) => {
+                            let line_offset = line.as_ptr() as usize - chunk.as_ptr() as usize;
+
+                            return Chunk { offset, error: Some((events.len(), line_offset, e)), events };
+                        },
+                    }
+                }
+
+                Chunk { offset, events, error: None }
+            })
+            .collect();
+
+        let mut events = Vec::with_capacity(chunks.iter().map(|chunk| chunk.events.len()).sum());
+        for chunk in chunks {
+            if let Some((line, line_offset, e)) = chunk.error {
+                // every line before the error was parsed into a single event
+                let line = events.len() + line + 1;
+
+                return Err(e.with_position(line, (chunk.offset + line_offset) as u64));
+            }
+
+            events.extend(chunk.events);
+        }
+
+        let session_starts = events.iter()
+            .enumerate()
+            .filter_map(|(i, e)| matches!(e.event(), EventType::BeginLog(_)).then_some(i))
+            .collect();
+
+        Ok(Self {
+            events,
+            session_starts,
+        })
+    }
+
+    /// all events in their original order
+    pub fn events(&self) -> &[Event<'a>] {
+        &self.events
+    }
+
+    /// indices of `BEGIN_LOG` events, each of them starts a new log session
+    pub fn session_starts(&self) -> &[usize] {
+        &self.session_starts
+    }
+
+    /// events of every log session, events preceding first `BEGIN_LOG` are returned as a separate session
+    pub fn sessions(&self) -> impl Iterator<Item = &[Event<'a>]> + '_ {
+        let leading = match self.session_starts.first() {
+            Some(0) => None,
+            _ if self.events.is_empty() => None,
+            _ => Some(0),
+        };
+
+        let starts = leading.into_iter()
+            .chain(self.session_starts.iter().copied());
+        let ends = starts.clone()
+            .skip(1)
+            .chain(iter::once(self.events.len()));
+
+        starts.zip(ends)
+            .map(|(start, end)| &self.events[start..end])
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn into_events(self) -> Vec<Event<'a>> {
+        self.events
+    }
+
+    /// convert into store that doesn't borrow from the parsed data
+    pub fn into_owned(self) -> EventStore<'static> {
+        EventStore {
+            events: self.events.into_iter().map(Event::into_owned).collect(),
+            session_starts: self.session_starts,
+        }
+    }
+}
+
+/// split `s` at line boundaries, into chunks of at least `CHUNK_SIZE` bytes (except the last one)
+pub(crate) fn split_chunks(s: &str) -> Vec<(usize, &str)> {
+    let mut chunks = Vec::with_capacity(s.len() / CHUNK_SIZE + 1);
+    let mut offset = 0;
+
+    while offset < s.len() {
+        let rest = &s.as_bytes()[offset..];
+        let len = rest.get(CHUNK_SIZE..)
+            .and_then(|tail| tail.iter().position(|&c| c == b'\n'))
+            .map_or(rest.len(), |i| CHUNK_SIZE + i + 1);
+
+        chunks.push((offset, &s[offset..offset + len]));
+        offset += len;
+    }
+
+    chunks
+}
@@ -5,10 +5,35 @@ use super::common::*;
 // ABILITY_INFO - abilityId, name, iconPath, interruptible, blockable
 #[derive(Debug, Clone, Getters, Deserialize, Serialize)]
 #[getset(get = "pub")]
-pub struct EventAbilityInfo {
+pub struct EventAbilityInfo<'a> {
     ability_id: AbilityId,
-    name: String,
-    icon_path: String,
+    #[getset(skip)]
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    #[getset(skip)]
+    #[serde(borrow)]
+    icon_path: Cow<'a, str>,
     interruptible: bool,
     blockable: bool,
-}
\ No newline at end of file
+}
+
+impl<'a> EventAbilityInfo<'a> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn icon_path(&self) -> &str {
+        &self.icon_path
+    }
+
+    /// convert into event that doesn't borrow from the parsed data
+    pub fn into_owned(self) -> EventAbilityInfo<'static> {
+        EventAbilityInfo {
+            ability_id: self.ability_id,
+            name: Cow::Owned(self.name.into_owned()),
+            icon_path: Cow::Owned(self.icon_path.into_owned()),
+            interruptible: self.interruptible,
+            blockable: self.blockable,
+        }
+    }
+}
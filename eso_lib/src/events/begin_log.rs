@@ -1,15 +1,63 @@
 use getset::Getters;
 
 use super::common::*;
+use crate::{GameVersion, LogSchema};
 
 // BEGIN_LOG - timeSinceEpochMS, logVersion, realmName, language, gameVersion
 #[derive(Debug, Clone, Getters, Deserialize, Serialize)]
 #[getset(get = "pub")]
-pub struct EventBeginLog {
+pub struct EventBeginLog<'a> {
     time: eso_serde::newtypes::EsoSystemTime,
-    log_version: String,
-    realm_name: String,
-    language: String,
-    game_version: String,
+    #[getset(skip)]
+    #[serde(borrow, serialize_with = "serialize_raw")]
+    log_version: Cow<'a, str>,
+    #[getset(skip)]
+    #[serde(borrow)]
+    realm_name: Cow<'a, str>,
+    #[getset(skip)]
+    #[serde(borrow)]
+    language: Cow<'a, str>,
+    #[getset(skip)]
+    #[serde(borrow)]
+    game_version: Cow<'a, str>,
+}
+
+impl<'a> EventBeginLog<'a> {
+    pub fn log_version(&self) -> &str {
+        &self.log_version
+    }
+
+    pub fn realm_name(&self) -> &str {
+        &self.realm_name
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn game_version(&self) -> &str {
+        &self.game_version
+    }
+
+    /// version of the game that wrote the log, `None` if it has unknown format
+    pub fn parsed_game_version(&self) -> Option<GameVersion> {
+        self.game_version.parse().ok()
+    }
+
+    /// field layout of the events following this one
+    pub fn schema(&self) -> LogSchema {
+        LogSchema::from_begin_log(self)
+    }
+
+    /// convert into event that doesn't borrow from the parsed data
+    pub fn into_owned(self) -> EventBeginLog<'static> {
+        EventBeginLog {
+            time: self.time,
+            log_version: Cow::Owned(self.log_version.into_owned()),
+            realm_name: Cow::Owned(self.realm_name.into_owned()),
+            language: Cow::Owned(self.language.into_owned()),
+            game_version: Cow::Owned(self.game_version.into_owned()),
+        }
+    }
 }
 
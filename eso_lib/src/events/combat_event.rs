@@ -19,7 +19,7 @@ pub struct EventCombatEvent {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(remote = "Self", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ActionResult {
     AbilityOnCooldown,
     Absorbed,
@@ -139,8 +139,14 @@ pub enum ActionResult {
 
     // not in docs
     LinkedCast,
+
+    /// value unknown to this version of the library
+    #[serde(skip)]
+    Other(String),
 }
 
+impl_other_variant!(ActionResult);
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum DamageType {
@@ -168,12 +174,12 @@ impl EventCombatEvent {
 impl ActionResult {
     #[inline]
     pub fn is_critical(&self) -> bool {
-        match self {
+        matches!(
+            self,
             Self::CriticalDamage
            |Self::CriticalHeal
            |Self::DotTickCritical
-           |Self::HotTickCritical => true,
-           _ => false,
-        }
+           |Self::HotTickCritical
+        )
     }
 }
@@ -1,7 +1,7 @@
 use super::*;
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(remote = "Self", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum EnchantType {
     AbsorbHealth,
     AbsorbMagicka,
@@ -42,4 +42,10 @@ pub enum EnchantType {
     ShockResistant,
     Stamina,
     StaminaRegen,
+
+    /// value unknown to this version of the library
+    #[serde(skip)]
+    Other(String),
 }
+
+impl_other_variant!(EnchantType);
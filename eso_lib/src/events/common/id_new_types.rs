@@ -27,27 +27,118 @@ pub struct MonsterId(pub IdType);
 #[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct StackCount(pub IdType);
 
-// TODO: make this an enum
-// PowertypeDaedric
-// PowertypeHealth
-// PowertypeHealthBonus
-// PowertypeInvalid
-// PowertypeMagicka
-// PowertypeMountStamina
-// PowertypeStamina
-// PowertypeUltimate
-// PowertypeWerewolf
-#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct PowerType(pub IdType);
+/// Implements enum over numeric ids used by the game, with `Unknown(IdType)` variant,
+/// ids unknown to this version of the library are deserialized into `Unknown`, instead of failing
+/// 
+/// `Display` writes human-readable name of the value
+macro_rules! id_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $t:ident {
+            $( $variant:ident = $id:literal => $name:literal, )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum $t {
+            $( $variant, )+
+            /// id unknown to this version of the library
+            Unknown(IdType),
+        }
 
-// TODO: make this an enum
-#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct RaceId(pub IdType);
+        impl $t {
+            /// every value known to this version of the library
+            pub const ALL: &'static [Self] = &[ $( Self::$variant, )+ ];
+
+            pub fn from_id(id: IdType) -> Self {
+                match id {
+                    $( $id => Self::$variant, )+
+                    _ => Self::Unknown(id),
+                }
+            }
 
-// TODO: make this an enum
-#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct ClassId(pub IdType);
+            /// id of the value, as written in the log
+            #[inline]
+            pub fn get(&self) -> IdType {
+                match self {
+                    $( Self::$variant => $id, )+
+                    Self::Unknown(id) => *id,
+                }
+            }
+        }
+
+        impl std::fmt::Display for $t {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $( Self::$variant => f.write_str($name), )+
+                    Self::Unknown(id) => write!(f, "Unknown ({id})"),
+                }
+            }
+        }
 
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                IdType::deserialize(deserializer).map(Self::from_id)
+            }
+        }
+
+        impl Serialize for $t {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                self.get().serialize(serializer)
+            }
+        }
+    };
+}
+
+id_enum! {
+    /// Resource used or restored by an ability, written by the game as combat mechanic flags
+    pub enum PowerType {
+        Invalid = 0 => "Invalid",
+        Magicka = 1 => "Magicka",
+        Werewolf = 2 => "Werewolf",
+        Stamina = 4 => "Stamina",
+        Ultimate = 8 => "Ultimate",
+        MountStamina = 16 => "Mount Stamina",
+        Health = 32 => "Health",
+        Daedric = 64 => "Daedric",
+        HealthBonus = 128 => "Health Bonus",
+    }
+}
+
+id_enum! {
+    pub enum RaceId {
+        None = 0 => "None",
+        Breton = 1 => "Breton",
+        Redguard = 2 => "Redguard",
+        Orc = 3 => "Orc",
+        Dunmer = 4 => "Dunmer",
+        Nord = 5 => "Nord",
+        Argonian = 6 => "Argonian",
+        Altmer = 7 => "Altmer",
+        Bosmer = 8 => "Bosmer",
+        Khajiit = 9 => "Khajiit",
+        Imperial = 10 => "Imperial",
+    }
+}
+
+id_enum! {
+    pub enum ClassId {
+        None = 0 => "None",
+        Dragonknight = 1 => "Dragonknight",
+        Sorcerer = 2 => "Sorcerer",
+        Nightblade = 3 => "Nightblade",
+        Warden = 4 => "Warden",
+        Necromancer = 5 => "Necromancer",
+        Templar = 6 => "Templar",
+        Arcanist = 117 => "Arcanist",
+    }
+}
 
 macro_rules! impl_getter {
     ($t:ty) => {
@@ -73,8 +164,5 @@ impl_getter! {
     AbilityId,
     TrackId,
     MonsterId,
-    StackCount,
-    PowerType,
-    RaceId,
-    ClassId
+    StackCount
 }
@@ -17,6 +17,10 @@ pub use quality::*;
 mod enchant_type;
 pub use enchant_type::*;
 
+mod other_variant;
+pub(crate) use other_variant::*;
+
 pub(crate) use eso_parser::*;
 pub(crate) use serde::{Deserialize, Serialize};
+pub(crate) use std::borrow::Cow;
 
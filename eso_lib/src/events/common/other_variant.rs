@@ -0,0 +1,93 @@
+use serde::de::{self, IntoDeserializer};
+
+use super::*;
+
+/// Implements `Deserialize` and `Serialize` for enum with `Other(String)` variant,
+/// unknown values are deserialized into `Other`, instead of failing
+///
+/// Enum must derive both traits with `#[serde(remote = "Self")]`,
+/// and mark `Other` variant with `#[serde(skip)]`
+///
+/// strict parsing functions reject events with `Other` values, see `EventType::unknown_values`
+macro_rules! impl_other_variant {
+    ($t:ty) => {
+        impl $t {
+            /// name of the enum, reported with its unknown values
+            pub const NAME: &'static str = stringify!($t);
+
+            /// value unknown to this version of the library, if it's `Other`
+            pub fn unknown_value(&self) -> Option<&str> {
+                match self {
+                    Self::Other(v) => Some(v),
+                    _ => None,
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $t {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let token = $crate::events::common::deserialize_cow_str(deserializer)?;
+
+                $crate::events::common::deserialize_known_variant(&token, Self::deserialize)
+                    .map_or_else(|| Ok(Self::Other(token.into_owned())), Ok)
+            }
+        }
+
+        impl serde::Serialize for $t {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                match self {
+                    Self::Other(v) => $crate::events::common::eso_serde::newtypes::RawToken(v).serialize(serializer),
+                    _ => Self::serialize(self, serializer),
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use impl_other_variant;
+
+/// deserialize string, borrowing it if deserializer allows it
+pub(crate) fn deserialize_cow_str<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Cow<'de, str>, D::Error> {
+    struct CowStrVisitor;
+
+    impl<'de> de::Visitor<'de> for CowStrVisitor {
+        type Value = Cow<'de, str>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a string")
+        }
+
+        fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+            Ok(Cow::Borrowed(v))
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(Cow::Owned(v.to_owned()))
+        }
+
+        fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+            Ok(Cow::Owned(v))
+        }
+    }
+
+    deserializer.deserialize_str(CowStrVisitor)
+}
+
+/// serialize string as a raw token, for unquoted string fields
+pub(crate) fn serialize_raw<S: serde::Serializer>(v: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    eso_serde::newtypes::RawToken(v).serialize(serializer)
+}
+
+/// try to match `token` against variants known by `deserialize`, `None` if it's unknown
+pub(crate) fn deserialize_known_variant<'a, T>(
+    token: &'a str,
+    deserialize: fn(de::value::StrDeserializer<'a, de::value::Error>) -> Result<T, de::value::Error>,
+) -> Option<T> {
+    deserialize(token.into_deserializer()).ok()
+}
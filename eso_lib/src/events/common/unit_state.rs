@@ -1,11 +1,10 @@
-use std::{str::FromStr, fmt::Display};
-
 use getset::Getters;
-use serde::{de::{Error, Unexpected}, Deserializer};
 
 use super::*;
 
-#[derive(Debug, Getters, Clone)]
+// written as `current/max` token
+#[derive(Debug, Getters, Clone, Deserialize, Serialize)]
+#[serde(rename = "$eso_serde::SlashSeparated")]
 #[getset(get = "pub")]
 pub struct CurrentMaxAttribute<T = Attribute>
 where
@@ -49,7 +48,9 @@ impl UnitState {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+// `None` is written as `*`, even at the end of the line
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename = "$eso_serde::ExplicitNone")]
 pub struct TargetUnitState(Option<UnitState>);
 
 #[derive(Debug, Clone, Getters, Deserialize, Serialize)]
@@ -60,66 +61,6 @@ pub struct Pos {
     rotation: f32,
 }
 
-struct StrVisitor;
-
-impl<'de> serde::de::Visitor<'de> for StrVisitor {
-    type Value = &'de str;
-
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(formatter, "a string")
-    }
-
-    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        Ok(v)
-    }
-}
-
-impl<'de, T> Deserialize<'de> for CurrentMaxAttribute<T>
-where
-    T: Clone + FromStr,
-    <T as FromStr>::Err: std::fmt::Debug,
-{
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        let str: &'de str = deserializer.deserialize_str(StrVisitor)?;
-        let mut reader = EsoLogReader::new_split(str, '/');
-        
-        let current_str = reader.next().ok_or_else(|| <D as Deserializer>::Error::invalid_length(1, &"eof while parsing value"))?;
-
-        const ERROR_MSG: &str = "unable to parse CurrentMaxAttribute";
-        let current: T = current_str
-            .parse()
-            .map_err(|_| <D as Deserializer>::Error::invalid_value(Unexpected::Other("unknown"), &ERROR_MSG))?;
-
-        let max: T = reader
-            .inner()
-            .parse()
-            .map_err(|_| <D as Deserializer>::Error::invalid_value(Unexpected::Other("unknown"), &ERROR_MSG))?;
-
-        Ok(Self {
-            current,
-            max,
-        })
-    }
-}
-
-impl<'de, T> Serialize for CurrentMaxAttribute<T>
-where
-    T: Clone + Display,
-{
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_str(&format!("{}/{}", &self.current, &self.max))
-    }
-}
-
 impl<'a> TargetUnitState {
     #[inline]
     pub fn get(&'a self, fallback: &'a UnitState) -> &'a UnitState {
@@ -127,18 +68,6 @@ impl<'a> TargetUnitState {
     }
 }
 
-impl Serialize for TargetUnitState {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        match self.0.as_ref() {
-            Some(v) => v.serialize(serializer),
-            None => "*".serialize(serializer),
-        }    
-    }
-}
-
 impl Pos {
     pub fn distance(&self, other: &Self) -> f32 {
         let x = self.x - other.x;
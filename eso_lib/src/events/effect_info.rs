@@ -9,12 +9,21 @@ pub struct EventEffectInfo {
     ability_id: AbilityId,
     effect_type: EffectType,
     status_effect_type: StatusEffectType,
-    // not used since U38
-    // no_effect_bar: bool,
+    // replaced `no_effect_bar` since U38, see `EventEffectInfoLegacy`
     effect_bar_display_behaviour: DisplayBehaviour,
     grants_synergy: Option<AbilityId>,
 }
 
+// EFFECT_INFO before U38 - abilityId, effectType, statusEffectType, noEffectBar, grantsSynergyAbilityId:optional
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct EventEffectInfoLegacy {
+    ability_id: AbilityId,
+    effect_type: EffectType,
+    status_effect_type: StatusEffectType,
+    no_effect_bar: bool,
+    grants_synergy: Option<AbilityId>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum EffectType {
@@ -24,7 +33,7 @@ pub enum EffectType {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(remote = "Self", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum StatusEffectType {
     Bleed,
     Blind,
@@ -48,8 +57,14 @@ pub enum StatusEffectType {
     Trauma,
     Weakness,
     Wound,
+
+    /// value unknown to this version of the library
+    #[serde(skip)]
+    Other(String),
 }
 
+impl_other_variant!(StatusEffectType);
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum DisplayBehaviour {
@@ -57,3 +72,21 @@ pub enum DisplayBehaviour {
     Always,
     Never,
 }
+
+impl From<EventEffectInfoLegacy> for EventEffectInfo {
+    fn from(e: EventEffectInfoLegacy) -> Self {
+        let effect_bar_display_behaviour = if e.no_effect_bar {
+            DisplayBehaviour::Never
+        } else {
+            DisplayBehaviour::Default
+        };
+
+        Self {
+            ability_id: e.ability_id,
+            effect_type: e.effect_type,
+            status_effect_type: e.status_effect_type,
+            effect_bar_display_behaviour,
+            grants_synergy: e.grants_synergy,
+        }
+    }
+}
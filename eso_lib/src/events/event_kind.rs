@@ -0,0 +1,214 @@
+use std::{fmt, ops::BitOr};
+
+use super::*;
+
+/// Generates `EventKind`, and accessors of `EventType` for every listed variant
+macro_rules! event_kinds {
+    (
+        $(
+            $variant:ident($t:ty) => $name:literal $(| $alias:literal)*,
+                $get:ident, $is:ident, $into:ident;
+        )+
+    ) => {
+        /// Type of the event, without its data
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum EventKind {
+            $( $variant, )+
+            /// event unknown to this version of the library
+            Unknown,
+        }
+
+        impl EventKind {
+            /// every kind, in declaration order
+            pub const ALL: &'static [EventKind] = &[ $( Self::$variant, )+ Self::Unknown ];
+
+            /// name of the event as written in the log, eg. `COMBAT_EVENT`,
+            /// `Unknown` is named `UNKNOWN`
+            pub fn name(self) -> &'static str {
+                match self {
+                    $( Self::$variant => $name, )+
+                    Self::Unknown => "UNKNOWN",
+                }
+            }
+
+            /// kind of the event named `name` in the log, `None` if it's unknown to this version of the library
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $( $name $(| $alias)* => Some(Self::$variant), )+
+                    _ => None,
+                }
+            }
+        }
+
+        impl<'a> EventType<'a> {
+            pub fn kind(&self) -> EventKind {
+                match self {
+                    $( Self::$variant(_) => EventKind::$variant, )+
+                    Self::Unknown { .. } => EventKind::Unknown,
+                }
+            }
+
+            $(
+                pub fn $get(&self) -> Option<&$t> {
+                    if let Self::$variant(e) = self {
+                        Some(e)
+                    } else {
+                        None
+                    }
+                }
+
+                pub fn $is(&self) -> bool {
+                    matches!(self, Self::$variant(_))
+                }
+
+                pub fn $into(self) -> Option<$t> {
+                    if let Self::$variant(e) = self {
+                        Some(e)
+                    } else {
+                        None
+                    }
+                }
+            )+
+        }
+    };
+}
+
+event_kinds! {
+    AbilityInfo(EventAbilityInfo<'a>) => "ABILITY_INFO",
+        ability_info, is_ability_info, into_ability_info;
+    BeginCast(EventBeginCast) => "BEGIN_CAST",
+        begin_cast, is_begin_cast, into_begin_cast;
+    BeginCombat(EventBeginCombat) => "BEGIN_COMBAT",
+        begin_combat, is_begin_combat, into_begin_combat;
+    BeginLog(EventBeginLog<'a>) => "BEGIN_LOG",
+        begin_log, is_begin_log, into_begin_log;
+    BeginTrial(EventBeginTrial) => "BEGIN_TRIAL",
+        begin_trial, is_begin_trial, into_begin_trial;
+    CombatEvent(EventCombatEvent) => "COMBAT_EVENT",
+        combat_event, is_combat_event, into_combat_event;
+    EffectChanged(EventEffectChanged) => "EFFECT_CHANGED",
+        effect_changed, is_effect_changed, into_effect_changed;
+    EffectInfo(EventEffectInfo) => "EFFECT_INFO",
+        effect_info, is_effect_info, into_effect_info;
+    EndCast(EventEndCast) => "END_CAST",
+        end_cast, is_end_cast, into_end_cast;
+    EndCombat(EventEndCombat) => "END_COMBAT",
+        end_combat, is_end_combat, into_end_combat;
+    EndLog(EventEndLog) => "END_LOG",
+        end_log, is_end_log, into_end_log;
+    EndTrial(EventEndTrial) => "END_TRIAL",
+        end_trial, is_end_trial, into_end_trial;
+    HealthRegen(EventHealthRegen) => "HEALTH_REGEN",
+        health_regen, is_health_regen, into_health_regen;
+    MapChanged(EventMapInfo<'a>) => "MAP_CHANGED" | "MAP_INFO",
+        map_info, is_map_info, into_map_info;
+    PlayerInfo(EventPlayerInfo) => "PLAYER_INFO",
+        player_info, is_player_info, into_player_info;
+    TrialInit(EventTrialInit) => "TRIAL_INIT",
+        trial_init, is_trial_init, into_trial_init;
+    UnitAdded(EventUnitAdded<'a>) => "UNIT_ADDED",
+        unit_added, is_unit_added, into_unit_added;
+    UnitChanged(EventUnitChanged<'a>) => "UNIT_CHANGED",
+        unit_changed, is_unit_changed, into_unit_changed;
+    UnitRemoved(EventUnitRemoved) => "UNIT_REMOVED",
+        unit_removed, is_unit_removed, into_unit_removed;
+    ZoneChanged(EventZoneInfo<'a>) => "ZONE_CHANGED" | "ZONE_INFO",
+        zone_info, is_zone_info, into_zone_info;
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Set of `EventKind`s
+///
+/// # Example usage
+/// ```
+/// # use eso_lib::{EventKind, EventKindSet};
+/// let set = EventKindSet::from(EventKind::CombatEvent) | EventKind::EffectChanged;
+///
+/// assert!(set.contains(EventKind::EffectChanged));
+/// assert!(!set.contains(EventKind::BeginCast));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EventKindSet(u32);
+
+// every kind has its own bit
+const _: () = assert!(EventKind::ALL.len() <= u32::BITS as usize);
+
+impl EventKindSet {
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn all() -> Self {
+        EventKind::ALL.iter().copied().collect()
+    }
+
+    #[inline]
+    const fn bit(kind: EventKind) -> u32 {
+        1 << kind as u32
+    }
+
+    /// this set, with `kind` added to it
+    pub const fn with(self, kind: EventKind) -> Self {
+        Self(self.0 | Self::bit(kind))
+    }
+
+    pub fn insert(&mut self, kind: EventKind) {
+        self.0 |= Self::bit(kind);
+    }
+
+    pub fn remove(&mut self, kind: EventKind) {
+        self.0 &= !Self::bit(kind);
+    }
+
+    #[inline]
+    pub const fn contains(&self, kind: EventKind) -> bool {
+        self.0 & Self::bit(kind) != 0
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = EventKind> + '_ {
+        EventKind::ALL.iter()
+            .copied()
+            .filter(|&kind| self.contains(kind))
+    }
+}
+
+impl From<EventKind> for EventKindSet {
+    fn from(kind: EventKind) -> Self {
+        Self::empty().with(kind)
+    }
+}
+
+impl FromIterator<EventKind> for EventKindSet {
+    fn from_iter<T: IntoIterator<Item = EventKind>>(iter: T) -> Self {
+        iter.into_iter().fold(Self::empty(), Self::with)
+    }
+}
+
+impl BitOr for EventKindSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOr<EventKind> for EventKindSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: EventKind) -> Self::Output {
+        self.with(rhs)
+    }
+}
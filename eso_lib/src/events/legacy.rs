@@ -0,0 +1,27 @@
+use super::*;
+
+/// Events whose layout differs in `LogSchema::Legacy`
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct LegacyEvent {
+    timestamp: EsoDuration,
+    event: LegacyEventType,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum LegacyEventType {
+    EffectInfo(EventEffectInfoLegacy),
+}
+
+impl From<LegacyEvent> for Event<'_> {
+    fn from(e: LegacyEvent) -> Self {
+        let event = match e.event {
+            LegacyEventType::EffectInfo(e) => EventType::EffectInfo(e.into()),
+        };
+
+        Self {
+            timestamp: e.timestamp,
+            event,
+        }
+    }
+}
@@ -6,8 +6,31 @@ use super::common::*;
 // MAP_INFO - id, name, texturePath
 #[derive(Debug, Clone, Getters, Deserialize, Serialize)]
 #[getset(get = "pub")]
-pub struct EventMapInfo {
+pub struct EventMapInfo<'a> {
     id: Id,
-    name: String,
-    texture_path: String,
-}
\ No newline at end of file
+    #[getset(skip)]
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    #[getset(skip)]
+    #[serde(borrow)]
+    texture_path: Cow<'a, str>,
+}
+
+impl<'a> EventMapInfo<'a> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn texture_path(&self) -> &str {
+        &self.texture_path
+    }
+
+    /// convert into event that doesn't borrow from the parsed data
+    pub fn into_owned(self) -> EventMapInfo<'static> {
+        EventMapInfo {
+            id: self.id,
+            name: Cow::Owned(self.name.into_owned()),
+            texture_path: Cow::Owned(self.texture_path.into_owned()),
+        }
+    }
+}
@@ -19,12 +19,19 @@ mod unit_added;
 mod unit_changed;
 mod unit_removed;
 mod zone_info;
+mod legacy;
+mod event_kind;
 
-use eso_parser::eso_serde::SerializeError;
+use std::{borrow::Cow, io::BufRead, sync::OnceLock};
+
+use eso_parser::{eso_serde::{deserializer::Deserializer, newtypes::{EsoDuration, RawToken}, ErrorKind, SerializeError}, EsoLogReader, EsoReaderTrait, UNGUARDED_PADDING};
 use rayon::{prelude::ParallelIterator, str::ParallelString};
 use serde::{Deserialize, Serialize};
 use getset::Getters;
 
+use crate::{log_schema::SchemaMap, EventReader, LogSchema};
+use legacy::LegacyEvent;
+
 pub use ability_info::*;
 pub use begin_cast::*;
 pub use begin_combat::*;
@@ -45,26 +52,53 @@ pub use unit_added::*;
 pub use unit_changed::*;
 pub use unit_removed::*;
 pub use zone_info::*;
+pub use event_kind::*;
 
 
 /// Event describing all possible events
 /// 
 /// you probably want to use one of the `Event::parse*` functions to deserialize it,
 /// or `Event::dump` to serialize it
+/// 
+/// other serde formats, eg. JSON, see it with named fields, and event types named like in the log
+/// 
+/// # JSON Lines
+/// library doesn't depend on `serde_json`, events are converted with it directly, one per line
+/// ```
+/// # use eso_lib::Event;
+/// let log = "9,BEGIN_COMBAT\n17,END_COMBAT";
+/// 
+/// let mut json_lines = String::new();
+/// for event in Event::parse_many_lenient(&log) {
+///     json_lines.push_str(&serde_json::to_string(&event.unwrap()).unwrap());
+///     json_lines.push('\n');
+/// }
+/// 
+/// // strings of the events are borrowed from `json_lines`, unless they contain escapes
+/// let events: Vec<Event> = json_lines.lines()
+///     .map(serde_json::from_str)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// 
+/// assert_eq!(events[1].dump().unwrap(), "17,END_COMBAT");
+/// ```
 #[derive(Debug, Clone, Getters, Deserialize, Serialize)]
 #[getset(get = "pub")]
-pub struct Event {
+pub struct Event<'a> {
     pub(crate) timestamp: eso_parser::eso_serde::newtypes::EsoDuration,
-    pub(crate) event: EventType,
+    #[serde(borrow)]
+    pub(crate) event: EventType<'a>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum EventType {
-    AbilityInfo(EventAbilityInfo),
+pub enum EventType<'a> {
+    #[serde(borrow)]
+    AbilityInfo(EventAbilityInfo<'a>),
     BeginCast(EventBeginCast),
     BeginCombat(EventBeginCombat),
-    BeginLog(EventBeginLog),
+    #[serde(borrow)]
+    BeginLog(EventBeginLog<'a>),
     BeginTrial(EventBeginTrial),
     CombatEvent(EventCombatEvent),
     EffectChanged(EventEffectChanged),
@@ -74,266 +108,404 @@ pub enum EventType {
     EndLog(EventEndLog),
     EndTrial(EventEndTrial),
     HealthRegen(EventHealthRegen),
-    #[serde(alias = "MAP_INFO")]
-    MapChanged(EventMapInfo),
+    #[serde(alias = "MAP_INFO", borrow)]
+    MapChanged(EventMapInfo<'a>),
     PlayerInfo(EventPlayerInfo),
     TrialInit(EventTrialInit),
-    UnitAdded(EventUnitAdded),
-    UnitChanged(EventUnitChanged),
+    #[serde(borrow)]
+    UnitAdded(EventUnitAdded<'a>),
+    #[serde(borrow)]
+    UnitChanged(EventUnitChanged<'a>),
     UnitRemoved(EventUnitRemoved),
-    #[serde(alias = "ZONE_INFO")]
-    ZoneChanged(EventZoneInfo),
+    #[serde(alias = "ZONE_INFO", borrow)]
+    ZoneChanged(EventZoneInfo<'a>),
+
+    /// event unknown to this version of the library, produced only by lenient parsing
+    ///
+    /// log format writes it back as it was read, other formats see it as `UNKNOWN` variant
+    Unknown {
+        #[serde(borrow)]
+        name: Cow<'a, str>,
+        raw_fields: Vec<Cow<'a, str>>,
+    },
 }
 
-impl Event {
-    /// parse single event
+impl<'a> Event<'a> {
+    /// parse single event, strings in returned event are borrowed from `s`
     #[inline]
-    pub fn parse(s: &str) -> Result<Self, eso_parser::eso_serde::Error> {
-        let mut deserializer = eso_parser::eso_serde::Deserializer::new(s);
+    pub fn parse(s: &'a str) -> Result<Self, eso_parser::eso_serde::Error> {
+        Self::parse_with_schema(s, LogSchema::Current)
+    }
 
-        let event = Event::deserialize(&mut deserializer)?;
+    /// parse single event, using field layout of the logs written with `schema`
+    /// 
+    /// functions parsing many events select schema on their own, from `BEGIN_LOG` events
+    #[inline]
+    pub fn parse_with_schema(s: &'a str, schema: LogSchema) -> Result<Self, eso_parser::eso_serde::Error> {
+        Self::recover_unknown(s, Self::deserialize_line(eso_parser::eso_serde::Deserializer::new(s), schema), false)
+    }
 
-        if deserializer.is_depleted() {
-            Ok(event)
-        } else {
-            Err(eso_parser::eso_serde::Error::ReaderNotExhausted)
-        }
+    /// parse single event, event types unknown to this version of the library
+    /// are returned as `EventType::Unknown`, and unknown enum values as their `Other` variant,
+    /// instead of failing
+    pub fn parse_lenient(s: &'a str) -> Result<Self, eso_parser::eso_serde::Error> {
+        Self::recover_unknown(s, Self::deserialize_line(eso_parser::eso_serde::Deserializer::new(s), LogSchema::Current), true)
     }
 
     /// parse many events
-    pub fn parse_many<'a, T: AsRef<str> + 'a>(s: &'a T) -> impl Iterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
+    pub fn parse_many<T: AsRef<str> + 'a>(s: &'a T) -> impl Iterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
         let s = s.as_ref();
 
-        unsafe { Self::_parse_many(s, s.len()) }
+        unsafe { Self::_parse_many(s, s.len(), LogSchema::default(), false) }
+    }
+
+    /// parse many events, event types unknown to this version of the library
+    /// are returned as `EventType::Unknown` instead of failing
+    pub fn parse_many_lenient<T: AsRef<str> + 'a>(s: &'a T) -> impl Iterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
+        let s = s.as_ref();
+
+        unsafe { Self::_parse_many(s, s.len(), LogSchema::default(), true) }
     }
     
+    /// parse many events, lines before the first `BEGIN_LOG` use field layout of `schema`
+    /// 
+    /// useful for parsing part of the log, eg. a range found with `LogIndex`
+    pub fn parse_many_with_schema<T: AsRef<str> + 'a>(s: &'a T, schema: LogSchema) -> impl Iterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
+        let s = s.as_ref();
+
+        unsafe { Self::_parse_many(s, s.len(), schema, false) }
+    }
+
     /// parse many events from `String` (parsing from `String` gives opportunity for niche optimization)
-    pub fn parse_many_string<'a>(s: &'a String) -> impl Iterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
-        unsafe { Self::_parse_many(s.as_str(), s.capacity()) }
+    pub fn parse_many_string(s: &'a String) -> impl Iterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
+        unsafe { Self::_parse_many(s.as_str(), s.capacity(), LogSchema::default(), false) }
+    }
+
+    /// parse events from any `BufRead` source, reading it in chunks
+    /// 
+    /// unlike `parse_many` this does not require whole log to be loaded into memory,
+    /// memory usage is bounded by chunk size and length of the longest line
+    pub fn parse_reader<R: BufRead>(reader: R) -> EventReader<R> {
+        EventReader::new(reader)
+    }
+
+    /// parse only events of `kinds`, lines of other kinds are skipped right after reading their name
+    /// 
+    /// event types unknown to this version of the library are yielded
+    /// as `EventType::Unknown` only if `kinds` contains `EventKind::Unknown`
+    /// 
+    /// # Example usage
+    /// ```no_run
+    /// # use eso_lib::{Event, EventKind, EventKindSet};
+    /// # let data = String::new();
+    /// let kinds = EventKindSet::from(EventKind::CombatEvent) | EventKind::EffectChanged;
+    /// 
+    /// for event in Event::parse_many_filtered(&data, kinds) {
+    ///     let _event = event.unwrap();
+    /// }
+    /// ```
+    pub fn parse_many_filtered<T: AsRef<str> + 'a>(s: &'a T, kinds: EventKindSet) -> impl Iterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
+        let s = s.as_ref();
+
+        unsafe { Self::_parse_many_filtered(s, s.len(), kinds) }
     }
 
     /// parse many events in parallel (uses `rayon` under the hood)
-    pub fn parse_many_par<'a, T: AsRef<str> + 'a>(s: &'a T) -> impl ParallelIterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
+    pub fn parse_many_par<T: AsRef<str> + 'a>(s: &'a T) -> impl ParallelIterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
+        let s = s.as_ref();
+
+        unsafe { Self::_parse_many_par(s, s.len(), false) }
+    }
+
+    /// parse many events in parallel (uses `rayon` under the hood), event types unknown to this version
+    /// of the library are returned as `EventType::Unknown` instead of failing
+    pub fn parse_many_par_lenient<T: AsRef<str> + 'a>(s: &'a T) -> impl ParallelIterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
         let s = s.as_ref();
 
-        unsafe { Self::_parse_many_par(s, s.len()) }
+        unsafe { Self::_parse_many_par(s, s.len(), true) }
     }
 
     /// parse many events in parallel from `String` (uses `rayon` under the hood, parsing from `String` gives opportunity for niche optimization)
-    pub fn parse_many_string_par<'a>(s: &'a String) -> impl ParallelIterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
-        unsafe { Self::_parse_many_par(s.as_str(), s.capacity()) }
+    pub fn parse_many_string_par(s: &'a String) -> impl ParallelIterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
+        unsafe { Self::_parse_many_par(s.as_str(), s.capacity(), false) }
     }
 
-    unsafe fn _parse_many<'a>(s: &'a str, capacity: usize) -> impl Iterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
-        // TODO: replace 32 with lane size
-        let end = s.as_ptr().add(capacity.saturating_sub(32));
+    /// # SAFETY
+    /// Caller must ensure that reading `capacity` bytes from the start of `s` is safe
+    pub(crate) unsafe fn _parse_many(s: &'a str, capacity: usize, mut schema: LogSchema, lenient: bool) -> impl Iterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
+        let end = s.as_ptr().add(capacity.saturating_sub(UNGUARDED_PADDING)) as usize;
+        let start = s.as_ptr();
 
         s.lines()
-         .map(move |s| {
-            if s.as_ptr().add(s.len()) < end {
-                Self::parse_unguarded(s)
-            } else {
-                Self::parse(s)
+         .enumerate()
+         .map(move |(i, s)| {
+            let result = Self::parse_line(s, end, schema, lenient);
+
+            if let Ok(event) = &result {
+                schema = schema.after(event);
             }
+
+            result.map_err(|e| e.with_position(i + 1, s.as_ptr().offset_from(start) as u64))
          })
     }
 
-    unsafe fn _parse_many_par<'a>(s: &'a str, capacity: usize) -> impl ParallelIterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
-        // TODO: replace 32 with lane size
-        let end = s.as_ptr().add(capacity.saturating_sub(32)) as usize;
+    /// # SAFETY
+    /// Caller must ensure that reading `capacity` bytes from the start of `s` is safe
+    pub(crate) unsafe fn _parse_many_filtered(s: &'a str, capacity: usize, kinds: EventKindSet) -> impl Iterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
+        let end = s.as_ptr().add(capacity.saturating_sub(UNGUARDED_PADDING)) as usize;
+        let start = s.as_ptr();
+        let lenient = kinds.contains(EventKind::Unknown);
 
-        s.par_lines()
-         .map(move |s| {
-            if (s.as_ptr().add(s.len()) as usize) < end {
-                Self::parse_unguarded(s)
-            } else {
-                Self::parse(s)
+        let mut schema = LogSchema::default();
+
+        s.lines()
+         .enumerate()
+         .filter_map(move |(i, s)| {
+            let kind = EventKind::from_name(line_event_name(s)).unwrap_or(EventKind::Unknown);
+
+            // `BEGIN_LOG` selects schema of the following lines, so it's always parsed
+            if !kinds.contains(kind) && kind != EventKind::BeginLog {
+                return None;
             }
-         })
-    }
 
-    #[allow(unused_unsafe)]
-    #[inline]
-    unsafe fn parse_unguarded(s: &str) -> Result<Self, eso_parser::eso_serde::Error> {
-        let mut deserializer = eso_parser::eso_serde::UnguardedDeserializer::new(s);
+            let result = Self::parse_line(s, end, schema, lenient);
 
-        let event = Event::deserialize(&mut deserializer)?;
+            if let Ok(event) = &result {
+                schema = schema.after(event);
+            }
 
-        if deserializer.is_depleted() {
-            Ok(event)
-        } else {
-            Err(eso_parser::eso_serde::Error::ReaderNotExhausted)
-        }
+            kinds.contains(kind).then(|| {
+                result.map_err(|e| e.with_position(i + 1, s.as_ptr().offset_from(start) as u64))
+            })
+         })
     }
 
-    /// Serializes this event to `String`
-    pub fn dump(&self) -> Result<String, SerializeError> {
-        let mut serializer = eso_parser::eso_serde::Serializer::new();
-        self.serialize(&mut serializer)?;
-        
-        Ok(serializer.into_string())
-    }
-}
+    /// # SAFETY
+    /// Caller must ensure that reading `capacity` bytes from the start of `s` is safe
+    pub(crate) unsafe fn _parse_many_par(s: &'a str, capacity: usize, lenient: bool) -> impl ParallelIterator<Item = Result<Self, eso_parser::eso_serde::Error>> + 'a {
+        let end = s.as_ptr().add(capacity.saturating_sub(UNGUARDED_PADDING)) as usize;
+        let data = s;
+        // lines are parsed out of order, so schema of every session has to be known upfront
+        let schemas = SchemaMap::scan(s);
+        // line numbers are not tracked in parallel, offsets of all newlines are found
+        // on the first error, and shared by the following ones
+        let newlines = OnceLock::new();
 
-// TODO: replace with macro
-impl EventType {
-    pub fn ability_info(&self) -> Option<&EventAbilityInfo> {
-        if let Self::AbilityInfo(e) = self {
-            Some(e)
-        } else {
-            None
-        }
+        s.par_lines()
+         .map(move |s| {
+            let offset = s.as_ptr() as usize - data.as_ptr() as usize;
+            let schema = schemas.get(offset);
+
+            Self::parse_line(s, end, schema, lenient).map_err(|e| {
+                let newlines: &Vec<usize> = newlines.get_or_init(|| {
+                    data.bytes()
+                        .enumerate()
+                        .filter_map(|(i, c)| (c == b'\n').then_some(i))
+                        .collect()
+                });
+                let line = newlines.partition_point(|&newline| newline < offset) + 1;
+
+                e.with_position(line, offset as u64)
+            })
+         })
     }
 
-    pub fn begin_cast(&self) -> Option<&EventBeginCast> {
-        if let Self::BeginCast(e) = self {
-            Some(e)
+    /// parse line, using `UnguardedDeserializer` if it ends before `end` address
+    /// 
+    /// # SAFETY
+    /// Caller must ensure that reading `UNGUARDED_PADDING` bytes past `end` address is safe
+    #[inline]
+    pub(crate) unsafe fn parse_line(s: &'a str, end: usize, schema: LogSchema, lenient: bool) -> Result<Self, eso_parser::eso_serde::Error> {
+        let result = if (s.as_ptr().add(s.len()) as usize) < end {
+            Self::parse_unguarded(s, schema)
         } else {
-            None
-        }
-    }
+            Self::deserialize_line(eso_parser::eso_serde::Deserializer::new(s), schema)
+        };
 
-    pub fn begin_combat(&self) -> Option<&EventBeginCombat> {
-        if let Self::BeginCombat(e) = self {
-            Some(e)
-        } else {
-            None
-        }
+        Self::recover_unknown(s, result, lenient)
     }
 
-    pub fn begin_log(&self) -> Option<&EventBeginLog> {
-        if let Self::BeginLog(e) = self {
-            Some(e)
-        } else {
-            None
-        }
+    /// # SAFETY
+    /// Caller must ensure that reading `UNGUARDED_PADDING` bytes past `s` is safe
+    #[allow(unused_unsafe)]
+    #[inline]
+    pub(crate) unsafe fn parse_unguarded(s: &'a str, schema: LogSchema) -> Result<Self, eso_parser::eso_serde::Error> {
+        Self::deserialize_line(eso_parser::eso_serde::UnguardedDeserializer::new(s), schema)
     }
 
-    pub fn begin_trial(&self) -> Option<&EventBeginTrial> {
-        if let Self::BeginTrial(e) = self {
-            Some(e)
-        } else {
-            None
-        }
-    }
+    #[inline]
+    fn deserialize_line<R: EsoReaderTrait<'a>>(mut deserializer: Deserializer<'a, R>, schema: LogSchema) -> Result<Self, eso_parser::eso_serde::Error> {
+        let event = match schema {
+            LogSchema::Current => Event::deserialize(&mut deserializer)?,
+            _ if schema.is_changed(line_event_name(deserializer.remaining())) => {
+                LegacyEvent::deserialize(&mut deserializer)?.into()
+            },
+            _ => Event::deserialize(&mut deserializer)?,
+        };
 
-    pub fn combat_event(&self) -> Option<&EventCombatEvent> {
-        if let Self::CombatEvent(e) = self {
-            Some(e)
+        if deserializer.is_depleted() {
+            Ok(event)
         } else {
-            None
+            Err(ErrorKind::ReaderNotExhausted.into())
         }
     }
 
-    pub fn effect_changed(&self) -> Option<&EventEffectChanged> {
-        if let Self::EffectChanged(e) = self {
-            Some(e)
-        } else {
-            None
+    /// if `lenient` is set and `result` failed because of unknown event type,
+    /// parse `s` again as `EventType::Unknown`, otherwise reject unknown enum values
+    pub(crate) fn recover_unknown(s: &'a str, result: Result<Self, eso_parser::eso_serde::Error>, lenient: bool) -> Result<Self, eso_parser::eso_serde::Error> {
+        match result {
+            Err(e) if lenient && e.is_unknown_event() => Self::parse_unknown(s).map_err(|_| e),
+            Ok(event) if !lenient => event.reject_unknown_values(),
+            result => result,
         }
     }
 
-    pub fn effect_info(&self) -> Option<&EventEffectInfo> {
-        if let Self::EffectInfo(e) = self {
-            Some(e)
-        } else {
-            None
+    /// fail if event has any enum value unknown to this version of the library
+    fn reject_unknown_values(self) -> Result<Self, eso_parser::eso_serde::Error> {
+        match self.event.unknown_values().first() {
+            Some(&(_, value)) => Err(eso_parser::eso_serde::Error::from(ErrorKind::UnknownVariant(value.to_owned()))
+                .with_token(value)
+                .with_event(self.kind().name())),
+            None => Ok(self),
         }
     }
 
-    pub fn end_cast(&self) -> Option<&EventEndCast> {
-        if let Self::EndCast(e) = self {
-            Some(e)
-        } else {
-            None
-        }
-    }
+    fn parse_unknown(s: &'a str) -> Result<Self, eso_parser::eso_serde::Error> {
+        let mut deserializer = eso_parser::eso_serde::Deserializer::new(s);
 
-    pub fn end_combat(&self) -> Option<&EventEndCombat> {
-        if let Self::EndCombat(e) = self {
-            Some(e)
-        } else {
-            None
-        }
+        let (timestamp, name) = <(EsoDuration, &str)>::deserialize(&mut deserializer)?;
+        let raw_fields = EsoLogReader::read_line(deserializer.remaining())
+            .map(Cow::Borrowed)
+            .collect();
+
+        Ok(Self {
+            timestamp,
+            event: EventType::Unknown {
+                name: Cow::Borrowed(name),
+                raw_fields,
+            },
+        })
     }
 
-    pub fn end_log(&self) -> Option<&EventEndLog> {
-        if let Self::EndLog(e) = self {
-            Some(e)
-        } else {
-            None
-        }
+    /// Serializes this event to `String`
+    pub fn dump(&self) -> Result<String, SerializeError> {
+        let mut serializer = eso_parser::eso_serde::Serializer::new();
+        self.serialize_line(&mut serializer)?;
+        
+        Ok(serializer.into_string())
     }
 
-    pub fn end_trial(&self) -> Option<&EventEndTrial> {
-        if let Self::EndTrial(e) = self {
-            Some(e)
+    /// Serializes this event as a single line, without the line terminator
+    pub fn serialize_line<W: std::io::Write>(&self, serializer: &mut eso_parser::eso_serde::Serializer<W>) -> Result<(), SerializeError> {
+        // unknown events are written back the same way they were read
+        if let EventType::Unknown { name, raw_fields } = &self.event {
+            self.timestamp.serialize(&mut *serializer)?;
+            RawToken(name).serialize(&mut *serializer)?;
+            raw_fields.iter().try_for_each(|field| RawToken(field).serialize(&mut *serializer))
         } else {
-            None
+            self.serialize(serializer)
         }
     }
 
-    pub fn health_regen(&self) -> Option<&EventHealthRegen> {
-        if let Self::HealthRegen(e) = self {
-            Some(e)
-        } else {
-            None
-        }
+    pub fn kind(&self) -> EventKind {
+        self.event.kind()
     }
 
-    pub fn map_info(&self) -> Option<&EventMapInfo> {
-        if let Self::MapChanged(e) = self {
-            Some(e)
-        } else {
-            None
+    /// convert into event that doesn't borrow from the parsed data
+    pub fn into_owned(self) -> Event<'static> {
+        Event {
+            timestamp: self.timestamp,
+            event: self.event.into_owned(),
         }
     }
+}
 
-    pub fn player_info(&self) -> Option<&EventPlayerInfo> {
-        if let Self::PlayerInfo(e) = self {
-            Some(e)
-        } else {
-            None
-        }
-    }
+/// name of the event, without parsing the line
+#[inline]
+pub(crate) fn line_event_name(line: &str) -> &str {
+    // name of the event is the second token of the line,
+    // both tokens are short, so plain loops beat `memchr`
+    let bytes = line.as_bytes();
+    let Some(start) = bytes.iter().position(|&c| c == b',').map(|i| i + 1) else {
+        return "";
+    };
 
-    pub fn trial_init(&self) -> Option<&EventTrialInit> {
-        if let Self::TrialInit(e) = self {
-            Some(e)
-        } else {
-            None
-        }
-    }
+    let len = bytes[start..].iter().position(|&c| c == b',').unwrap_or(bytes.len() - start);
 
-    pub fn unit_added(&self) -> Option<&EventUnitAdded> {
-        if let Self::UnitAdded(e) = self {
-            Some(e)
-        } else {
-            None
+    &line[start..start + len]
+}
+
+impl<'a> EventType<'a> {
+    /// convert into event that doesn't borrow from the parsed data
+    pub fn into_owned(self) -> EventType<'static> {
+        use EventType::*;
+
+        match self {
+            AbilityInfo(e) => AbilityInfo(e.into_owned()),
+            BeginCast(e) => BeginCast(e),
+            BeginCombat(e) => BeginCombat(e),
+            BeginLog(e) => BeginLog(e.into_owned()),
+            BeginTrial(e) => BeginTrial(e),
+            CombatEvent(e) => CombatEvent(e),
+            EffectChanged(e) => EffectChanged(e),
+            EffectInfo(e) => EffectInfo(e),
+            EndCast(e) => EndCast(e),
+            EndCombat(e) => EndCombat(e),
+            EndLog(e) => EndLog(e),
+            EndTrial(e) => EndTrial(e),
+            HealthRegen(e) => HealthRegen(e),
+            MapChanged(e) => MapChanged(e.into_owned()),
+            PlayerInfo(e) => PlayerInfo(e),
+            TrialInit(e) => TrialInit(e),
+            UnitAdded(e) => UnitAdded(e.into_owned()),
+            UnitChanged(e) => UnitChanged(e.into_owned()),
+            UnitRemoved(e) => UnitRemoved(e),
+            ZoneChanged(e) => ZoneChanged(e.into_owned()),
+            Unknown { name, raw_fields } => Unknown {
+                name: Cow::Owned(name.into_owned()),
+                raw_fields: raw_fields.into_iter()
+                    .map(|field| Cow::Owned(field.into_owned()))
+                    .collect(),
+            },
         }
     }
 
-    pub fn unit_changed(&self) -> Option<&EventUnitChanged> {
-        if let Self::UnitChanged(e) = self {
-            Some(e)
+    /// name of the event if it's unknown to this version of the library
+    pub fn unknown_name(&self) -> Option<&str> {
+        if let Self::Unknown { name, .. } = self {
+            Some(name)
         } else {
             None
         }
     }
 
-    pub fn unit_removed(&self) -> Option<&EventUnitRemoved> {
-        if let Self::UnitRemoved(e) = self {
-            Some(e)
-        } else {
-            None
+    /// enum values unknown to this version of the library (`Other` variants),
+    /// as pairs of enum name and the value, eg. `("ActionResult", "NEW_ACTION_RESULT")`
+    pub fn unknown_values(&self) -> Vec<(&'static str, &str)> {
+        fn push<'v>(values: &mut Vec<(&'static str, &'v str)>, name: &'static str, value: Option<&'v str>) {
+            if let Some(value) = value {
+                values.push((name, value));
+            }
         }
-    }
 
-    pub fn zone_info(&self) -> Option<&EventZoneInfo> {
-        if let Self::ZoneChanged(e) = self {
-            Some(e)
-        } else {
-            None
+        let mut values = Vec::new();
+
+        match self {
+            Self::CombatEvent(e) => {
+                push(&mut values, ActionResult::NAME, e.action_result().unknown_value());
+            },
+            Self::EffectInfo(e) => {
+                push(&mut values, StatusEffectType::NAME, e.status_effect_type().unknown_value());
+            },
+            Self::PlayerInfo(e) => {
+                for eq in e.equipment_info() {
+                    push(&mut values, common::Trait::NAME, eq.trait_().unknown_value());
+                    push(&mut values, common::EnchantType::NAME, eq.enchant_type().unknown_value());
+                }
+            },
+            _ => {},
         }
+
+        values
     }
 }
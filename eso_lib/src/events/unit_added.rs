@@ -5,7 +5,7 @@ use super::common::*;
 // UNIT_ADDED - unitId, unitType, isLocalPlayer, playerPerSessionId, monsterId, isBoss, classId, raceId, name, displayName, characterId, level, championPoints, ownerUnitId, reaction, isGroupedWithLocalPlayer
 #[derive(Debug, Clone, Getters, Deserialize, Serialize)]
 #[getset(get = "pub")]
-pub struct EventUnitAdded {
+pub struct EventUnitAdded<'a> {
     unit_id: UnitId,
     unit_type: UnitType,
     is_local_player: bool,
@@ -14,8 +14,12 @@ pub struct EventUnitAdded {
     is_boss: bool,
     class_id: ClassId,
     race_id: RaceId,
-    name: String,
-    display_name: String,
+    #[getset(skip)]
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    #[getset(skip)]
+    #[serde(borrow)]
+    display_name: Cow<'a, str>,
     character_id: Id,
     level: Attribute,
     champion_points: Attribute,
@@ -24,6 +28,38 @@ pub struct EventUnitAdded {
     is_grouped_with_local_player: bool,
 }
 
+impl<'a> EventUnitAdded<'a> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    /// convert into event that doesn't borrow from the parsed data
+    pub fn into_owned(self) -> EventUnitAdded<'static> {
+        EventUnitAdded {
+            unit_id: self.unit_id,
+            unit_type: self.unit_type,
+            is_local_player: self.is_local_player,
+            player_per_session_id: self.player_per_session_id,
+            monster_id: self.monster_id,
+            is_boss: self.is_boss,
+            class_id: self.class_id,
+            race_id: self.race_id,
+            name: Cow::Owned(self.name.into_owned()),
+            display_name: Cow::Owned(self.display_name.into_owned()),
+            character_id: self.character_id,
+            level: self.level,
+            champion_points: self.champion_points,
+            owner_id: self.owner_id,
+            reaction: self.reaction,
+            is_grouped_with_local_player: self.is_grouped_with_local_player,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum UnitType {
@@ -5,17 +5,48 @@ use super::common::*;
 // UNIT_CHANGED - unitId, classId, raceId, name, displayName, characterId, level, championPoints, ownerUnitId, reaction, isGroupedWithLocalPlayer
 #[derive(Debug, Clone, Getters, Deserialize, Serialize)]
 #[getset(get = "pub")]
-pub struct EventUnitChanged {
+pub struct EventUnitChanged<'a> {
     unit_id: UnitId,
     class_id: ClassId,
     race_id: RaceId,
-    name: String,
-    display_name: String,
+    #[getset(skip)]
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    #[getset(skip)]
+    #[serde(borrow)]
+    display_name: Cow<'a, str>,
     character_id: Id,
     level: Attribute,
     champion_points: Attribute,
-    owner_id: Id,
+    owner_id: UnitId,
     reaction: super::UnitReactionType,
     is_grouped_with_local_player: bool,
 }
 
+impl<'a> EventUnitChanged<'a> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    /// convert into event that doesn't borrow from the parsed data
+    pub fn into_owned(self) -> EventUnitChanged<'static> {
+        EventUnitChanged {
+            unit_id: self.unit_id,
+            class_id: self.class_id,
+            race_id: self.race_id,
+            name: Cow::Owned(self.name.into_owned()),
+            display_name: Cow::Owned(self.display_name.into_owned()),
+            character_id: self.character_id,
+            level: self.level,
+            champion_points: self.champion_points,
+            owner_id: self.owner_id,
+            reaction: self.reaction,
+            is_grouped_with_local_player: self.is_grouped_with_local_player,
+        }
+    }
+}
+
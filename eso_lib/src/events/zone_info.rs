@@ -5,12 +5,29 @@ use super::common::*;
 // ZONE_INFO - id, name, dungeonDifficulty
 #[derive(Debug, Clone, Getters, Deserialize, Serialize)]
 #[getset(get = "pub")]
-pub struct EventZoneInfo {
+pub struct EventZoneInfo<'a> {
     id: Id,
-    name: String,
+    #[getset(skip)]
+    #[serde(borrow)]
+    name: Cow<'a, str>,
     dungeon_difficulty: DungeonDifficulty,
 }
 
+impl<'a> EventZoneInfo<'a> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// convert into event that doesn't borrow from the parsed data
+    pub fn into_owned(self) -> EventZoneInfo<'static> {
+        EventZoneInfo {
+            id: self.id,
+            name: Cow::Owned(self.name.into_owned()),
+            dungeon_difficulty: self.dungeon_difficulty,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum DungeonDifficulty {
@@ -3,7 +3,10 @@
 //! The Elder Scrolls Online encounter log format
 //! 
 //! # Example usage
-//! ```
+//! ```no_run
+//! # use std::fs;
+//! # use eso_lib::Event;
+//! # let path = "Encounter.log";
 //! let data = fs::read_to_string(path).unwrap();
 //! let events = Event::parse_many(&data)
 //!     .collect::<Result<Vec<Event>, _>>()
@@ -13,9 +16,27 @@
 
 pub mod events;
 pub mod state;
+pub mod event_reader;
+pub mod log_follower;
+pub mod log_file;
+pub mod log_schema;
+pub mod event_store;
+pub mod raw_event;
+pub mod log_writer;
+pub mod log_index;
+pub mod event_cache;
 
 pub use events::*;
 pub use state::*;
+pub use event_reader::*;
+pub use log_follower::*;
+pub use log_file::*;
+pub use log_schema::*;
+pub use event_store::*;
+pub use raw_event::*;
+pub use log_writer::*;
+pub use log_index::*;
+pub use event_cache::*;
 
 pub use eso_parser;
 
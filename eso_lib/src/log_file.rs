@@ -0,0 +1,92 @@
+use std::{fs::File, ops::Range, path::Path};
+
+use eso_parser::eso_serde::Error;
+use memmap2::Mmap;
+use rayon::prelude::ParallelIterator;
+
+use crate::{Event, EventStore, LogIndex, LogSchema};
+
+/// Smallest page size of supported platforms, every real page size is a multiple of it
+const PAGE_SIZE: usize = 4096;
+
+/// Log file mapped into memory
+/// 
+/// Events are parsed straight from the mapping, without copying the data.
+/// Bytes between end of the file and the end of its last page are zeroed by the OS,
+/// and are safe to read, which lets every line but the last few
+/// to be parsed with `UnguardedDeserializer`
+pub struct LogFile {
+    // empty files can't be mapped
+    mmap: Option<Mmap>,
+}
+
+impl LogFile {
+    /// map log file into memory, and validate that it is a valid UTF-8
+    /// 
+    /// # SAFETY
+    /// File must not be modified (eg. truncated) as long as returned `LogFile` exists,
+    /// in best case this will cause `SIGBUS`
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = File::open(path)?;
+
+        if file.metadata()?.len() == 0 {
+            return Ok(Self { mmap: None });
+        }
+
+        let mmap = Mmap::map(&file)?;
+        std::str::from_utf8(&mmap)?;
+
+        Ok(Self { mmap: Some(mmap) })
+    }
+
+    /// content of the file
+    pub fn as_str(&self) -> &str {
+        match &self.mmap {
+            // SAFETY: validated in `open`
+            Some(mmap) => unsafe { std::str::from_utf8_unchecked(mmap) },
+            None => "",
+        }
+    }
+
+    /// parse all events from the file
+    pub fn events(&self) -> impl Iterator<Item = Result<Event<'_>, Error>> + '_ {
+        // SAFETY: whole last page of the mapping is safe to read
+        unsafe { Event::_parse_many(self.as_str(), self.readable_len(), LogSchema::default(), false) }
+    }
+
+    /// parse all events from the file in parallel (uses `rayon` under the hood)
+    pub fn events_par(&self) -> impl ParallelIterator<Item = Result<Event<'_>, Error>> + '_ {
+        // SAFETY: same as above
+        unsafe { Event::_parse_many_par(self.as_str(), self.readable_len(), false) }
+    }
+
+    /// parse all events from the file in parallel, keeping their original order
+    pub fn event_store(&self) -> Result<EventStore<'_>, Error> {
+        // SAFETY: same as above
+        unsafe { EventStore::_parse(self.as_str(), self.readable_len(), false) }
+    }
+
+    /// parse events from the byte `range` of the file, eg. found with `LogIndex`,
+    /// lines before the first `BEGIN_LOG` use field layout of `schema`
+    /// 
+    /// positions of the errors are relative to the start of the range
+    /// 
+    /// # Panics
+    /// if `range` is out of bounds, or doesn't start and end at char boundaries
+    pub fn events_in(&self, range: Range<u64>, schema: LogSchema) -> impl Iterator<Item = Result<Event<'_>, Error>> + '_ {
+        let s = &self.as_str()[range.start as usize..range.end as usize];
+
+        // SAFETY: same as above, range starts within the mapping
+        unsafe { Event::_parse_many(s, self.readable_len() - range.start as usize, schema, false) }
+    }
+
+    /// build index of the file
+    pub fn index(&self) -> LogIndex {
+        LogIndex::build(self.as_str())
+    }
+
+    /// length of the file, rounded up to the page size
+    fn readable_len(&self) -> usize {
+        self.as_str().len().next_multiple_of(PAGE_SIZE)
+    }
+}
@@ -0,0 +1,241 @@
+use std::{fs::File, io::{ErrorKind, Read}, path::{Path, PathBuf}, time::Duration};
+
+use eso_parser::eso_serde::Error;
+
+use crate::{event_reader::LineBuffer, Event, LogSchema, State};
+
+/// Amount of bytes read from the file at once
+const READ_CHUNK_SIZE: usize = 1 << 20;
+
+/// Follows log file that is still being written to (like `tail -f`)
+/// 
+/// Iterating over it yields events for every complete line written so far,
+/// every yielded event is also passed to the internal `State`.
+/// Once iterator returns `None` all the lines written so far were consumed,
+/// calling `next` again later will yield lines that were written in the meantime
+/// 
+/// If the file gets truncated or replaced (eg. by log rotation),
+/// `FollowEvent::Restarted` is yielded, and file is read again from the beginning
+/// with `State` reset the same way as on `BEGIN_LOG`
+/// 
+/// # Example usage
+/// ```no_run
+/// # use std::time::Duration;
+/// # use eso_lib::{FollowEvent, LogFollower};
+/// let mut follower = LogFollower::open("Encounter.log").unwrap();
+/// 
+/// loop {
+///     for event in &mut follower {
+///         match event.unwrap() {
+///             FollowEvent::Event(_event) => { /* ... */ },
+///             FollowEvent::Restarted => println!("log was replaced"),
+///         }
+///     }
+/// 
+///     println!("units: {}", follower.state().entities().len());
+///     std::thread::sleep(Duration::from_millis(250));
+/// }
+/// ```
+pub struct LogFollower {
+    path: PathBuf,
+    file: File,
+    // amount of bytes read from `file`
+    position: u64,
+    lines: LineBuffer,
+    read_buf: Box<[u8]>,
+    state: State,
+    lenient: bool,
+    // schema selected by the last `BEGIN_LOG` event
+    schema: LogSchema,
+    // first line of the file, used to tell if the file was rewritten
+    header: Vec<u8>,
+    // whole file was read, it has to be checked for replacement before reading more
+    caught_up: bool,
+    // replacement was noticed, but not reported yet
+    restarted: bool,
+}
+
+/// Item yielded by `LogFollower`
+// it's moved out right away, boxing would only add an allocation per event
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+pub enum FollowEvent {
+    Event(Event<'static>),
+    /// file was truncated or replaced, following events are read from its beginning
+    Restarted,
+}
+
+impl LogFollower {
+    /// start following log file from its beginning
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref().to_owned();
+        let file = File::open(&path)?;
+
+        Ok(Self {
+            path,
+            file,
+            position: 0,
+            lines: LineBuffer::with_capacity(READ_CHUNK_SIZE),
+            read_buf: vec![0; READ_CHUNK_SIZE].into_boxed_slice(),
+            state: State::new(),
+            lenient: false,
+            schema: LogSchema::default(),
+            header: Vec::new(),
+            caught_up: false,
+            restarted: false,
+        })
+    }
+
+    /// yield events of types unknown to this version of the library
+    /// as `EventType::Unknown`, instead of failing
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
+    /// state built from all events yielded so far
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// path of the followed file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// wait until next event is available, checking the file every `poll_interval`
+    pub fn next_blocking(&mut self, poll_interval: Duration) -> Result<FollowEvent, Error> {
+        loop {
+            match self.next() {
+                Some(event) => return event,
+                None => std::thread::sleep(poll_interval),
+            }
+        }
+    }
+
+    /// read next chunk of the file, returns `0` if there is nothing new to read
+    fn read_chunk(&mut self) -> Result<usize, Error> {
+        // file could be rewritten past the old position since the last poll,
+        // so it has to be checked before reading anything
+        if self.caught_up {
+            self.caught_up = false;
+
+            if self.was_replaced()? {
+                self.restart()?;
+            }
+        }
+
+        loop {
+            match self.file.read(&mut self.read_buf) {
+                Ok(0) => {
+                    if !self.was_replaced()? {
+                        self.caught_up = true;
+                        return Ok(0);
+                    }
+
+                    self.restart()?;
+                },
+                Ok(read) => {
+                    self.position += read as u64;
+                    self.lines.extend(&self.read_buf[..read]);
+
+                    return Ok(read);
+                },
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// check if file under `self.path` was truncated or replaced with another one
+    fn was_replaced(&self) -> Result<bool, Error> {
+        let metadata = match std::fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            // file is being rotated, keep reading the old one until new one is created
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+
+        if metadata.len() < self.position {
+            return Ok(true);
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            let current = self.file.metadata()?;
+            if (current.dev(), current.ino()) != (metadata.dev(), metadata.ino()) {
+                return Ok(true);
+            }
+        }
+
+        // truncated and written again, with a new `BEGIN_LOG`
+        self.header_changed()
+    }
+
+    fn header_changed(&self) -> Result<bool, Error> {
+        if self.header.is_empty() {
+            return Ok(false);
+        }
+
+        let mut header = vec![0; self.header.len()];
+        let mut file = File::open(&self.path)?;
+
+        match file.read_exact(&mut header) {
+            Ok(()) => Ok(header != self.header),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(true),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn restart(&mut self) -> Result<(), Error> {
+        self.file = File::open(&self.path)?;
+        self.position = 0;
+        self.lines.clear();
+        self.state.reset();
+        self.schema = LogSchema::default();
+        self.header.clear();
+        self.restarted = true;
+
+        Ok(())
+    }
+}
+
+impl Iterator for LogFollower {
+    type Item = Result<FollowEvent, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if std::mem::take(&mut self.restarted) {
+                return Some(Ok(FollowEvent::Restarted));
+            }
+
+            if let Some((position, line)) = self.lines.next_line() {
+                if let (0, Ok(line)) = (position.offset(), &line) {
+                    self.header = line.as_bytes().to_vec();
+                }
+
+                // SAFETY: `LineBuffer` keeps `UNGUARDED_PADDING` bytes of spare capacity past its data
+                let event = line
+                    .and_then(|line| Event::recover_unknown(line, unsafe { Event::parse_unguarded(line, self.schema) }, self.lenient))
+                    .map(Event::into_owned)
+                    .map_err(|e| position.attach(e));
+
+                if let Ok(event) = &event {
+                    self.schema = self.schema.after(event);
+                    self.state.handle_event(event);
+                }
+
+                return Some(event.map(FollowEvent::Event));
+            }
+
+            // incomplete line at the end is kept, until rest of it is written
+            match self.read_chunk() {
+                Ok(0) if !self.restarted => return None,
+                Ok(_) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
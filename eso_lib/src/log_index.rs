@@ -0,0 +1,418 @@
+use std::{fs::File, io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write}, ops::Range, path::{Path, PathBuf}, time::{Duration, SystemTime}};
+
+use getset::Getters;
+
+use crate::{events::line_event_name, Event, EventKind, LogSchema};
+
+/// Approximate distance in bytes between timestamp checkpoints
+const CHECKPOINT_INTERVAL: u64 = 1 << 20;
+
+/// Identifies sidecar file format, followed by its version
+const MAGIC: &[u8; 8] = b"ESOLOGIX";
+const VERSION: u32 = 2;
+
+/// Byte offsets of the important lines of the log, for random access into it
+///
+/// Index is built in a single pass, that reads only timestamp and name of every line,
+/// and can be saved as a sidecar file next to the log (`Encounter.log.idx`).
+/// Byte ranges returned by it can be read and parsed without touching the rest of the log
+///
+/// # Example usage
+/// ```no_run
+/// # use std::fs::File;
+/// # use eso_lib::{Event, LogIndex};
+/// let index = LogIndex::load_or_build("Encounter.log").unwrap();
+///
+/// let fight = index.fight(12).unwrap();
+/// let data = index.read_range(File::open("Encounter.log").unwrap(), fight.clone()).unwrap();
+///
+/// for event in Event::parse_many_with_schema(&data, index.schema_at(fight.start)) {
+///     let _event = event.unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct LogIndex {
+    /// length of the log the index was built from
+    log_len: u64,
+    /// modification time of the log the index was built from, if it was built from a file
+    log_modified: Option<SystemTime>,
+    /// every `BEGIN_LOG` line
+    sessions: Vec<IndexedSession>,
+    /// every `BEGIN_COMBAT`, `END_COMBAT`, `ZONE_CHANGED` and `BEGIN_TRIAL` line
+    markers: Vec<IndexMarker>,
+    /// first line past every `CHECKPOINT_INTERVAL` bytes
+    checkpoints: Vec<IndexCheckpoint>,
+}
+
+/// Log session, started by `BEGIN_LOG` line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct IndexedSession {
+    offset: u64,
+    schema: LogSchema,
+}
+
+/// Line of the event, that is worth jumping to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct IndexMarker {
+    kind: EventKind,
+    /// offset of the line start
+    offset: u64,
+    /// offset past the line terminator
+    end: u64,
+    timestamp: Duration,
+}
+
+/// Timestamp of the line, used to find lines by their timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct IndexCheckpoint {
+    offset: u64,
+    timestamp: Duration,
+}
+
+impl LogIndex {
+    /// build index of the whole log
+    pub fn build(s: &str) -> Self {
+        let mut index = Self::default();
+        let mut offset = 0;
+
+        for line in s.split_inclusive('\n') {
+            index.push_line(offset, line.as_bytes());
+            offset += line.len() as u64;
+        }
+
+        index.log_len = offset;
+        index
+    }
+
+    /// build index of the whole log, reading it line by line
+    pub fn from_reader<R: BufRead>(mut reader: R) -> io::Result<Self> {
+        let mut index = Self::default();
+        let mut line = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 { break; }
+
+            index.push_line(offset, &line);
+            offset += read as u64;
+        }
+
+        index.log_len = offset;
+        Ok(index)
+    }
+
+    /// path of the sidecar file of the log at `log`, eg. `Encounter.log.idx`
+    pub fn sidecar_path<P: AsRef<Path>>(log: P) -> PathBuf {
+        let mut path = log.as_ref().as_os_str().to_owned();
+        path.push(".idx");
+
+        path.into()
+    }
+
+    /// load index from the sidecar file of the log at `log`,
+    /// or build it and save it if sidecar is missing or stale
+    /// (length or modification time of the log changed)
+    ///
+    /// failing to save the sidecar is not an error, built index is returned anyway
+    pub fn load_or_build<P: AsRef<Path>>(log: P) -> io::Result<Self> {
+        let log = log.as_ref();
+        let sidecar = Self::sidecar_path(log);
+        let metadata = std::fs::metadata(log)?;
+        let log_modified = metadata.modified().ok();
+
+        if let Ok(index) = Self::load(&sidecar) {
+            if index.log_len == metadata.len() && log_modified.is_some() && index.log_modified == log_modified {
+                return Ok(index);
+            }
+        }
+
+        let mut index = Self::from_reader(BufReader::with_capacity(1 << 20, File::open(log)?))?;
+        index.log_modified = log_modified;
+        let _ = index.save(&sidecar);
+
+        Ok(index)
+    }
+
+    /// load index saved with `save`
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut magic = [0; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC || read_u32(&mut reader)? != VERSION {
+            return Err(invalid_data("not a log index, or unsupported version"));
+        }
+
+        let log_len = read_u64(&mut reader)?;
+        let log_modified = match read_u8(&mut reader)? {
+            0 => None,
+            1 => Some(SystemTime::UNIX_EPOCH + Duration::new(read_u64(&mut reader)?, read_u32(&mut reader)?)),
+            _ => return Err(invalid_data("invalid modification time")),
+        };
+
+        let sessions = read_vec(&mut reader, |r| {
+            let offset = read_u64(r)?;
+            let schema = match read_u8(r)? {
+                0 => LogSchema::Legacy,
+                1 => LogSchema::Current,
+                _ => return Err(invalid_data("invalid schema")),
+            };
+
+            Ok(IndexedSession { offset, schema })
+        })?;
+
+        let markers = read_vec(&mut reader, |r| {
+            // kinds are stored by their names, their order changes with new events
+            let kind = EventKind::from_name(&read_str(r)?)
+                .ok_or_else(|| invalid_data("invalid event kind"))?;
+
+            Ok(IndexMarker {
+                kind,
+                offset: read_u64(r)?,
+                end: read_u64(r)?,
+                timestamp: Duration::from_millis(read_u64(r)?),
+            })
+        })?;
+
+        let checkpoints = read_vec(&mut reader, |r| {
+            Ok(IndexCheckpoint {
+                offset: read_u64(r)?,
+                timestamp: Duration::from_millis(read_u64(r)?),
+            })
+        })?;
+
+        Ok(Self {
+            log_len,
+            log_modified,
+            sessions,
+            markers,
+            checkpoints,
+        })
+    }
+
+    /// save index into the file at `path`, usually `sidecar_path` of the log
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&self.log_len.to_le_bytes())?;
+        match self.log_modified.and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok()) {
+            Some(modified) => {
+                writer.write_all(&[1])?;
+                writer.write_all(&modified.as_secs().to_le_bytes())?;
+                writer.write_all(&modified.subsec_nanos().to_le_bytes())?;
+            },
+            None => writer.write_all(&[0])?,
+        }
+
+        writer.write_all(&(self.sessions.len() as u64).to_le_bytes())?;
+        for session in &self.sessions {
+            writer.write_all(&session.offset.to_le_bytes())?;
+            writer.write_all(&[matches!(session.schema, LogSchema::Current) as u8])?;
+        }
+
+        writer.write_all(&(self.markers.len() as u64).to_le_bytes())?;
+        for marker in &self.markers {
+            write_str(&mut writer, marker.kind.name())?;
+            writer.write_all(&marker.offset.to_le_bytes())?;
+            writer.write_all(&marker.end.to_le_bytes())?;
+            writer.write_all(&(marker.timestamp.as_millis() as u64).to_le_bytes())?;
+        }
+
+        writer.write_all(&(self.checkpoints.len() as u64).to_le_bytes())?;
+        for checkpoint in &self.checkpoints {
+            writer.write_all(&checkpoint.offset.to_le_bytes())?;
+            writer.write_all(&(checkpoint.timestamp.as_millis() as u64).to_le_bytes())?;
+        }
+
+        writer.flush()
+    }
+
+    /// byte range of the session `n`, from its `BEGIN_LOG` to the next one
+    pub fn session(&self, n: usize) -> Option<Range<u64>> {
+        let start = self.sessions.get(n)?.offset;
+        let end = self.sessions.get(n + 1).map_or(self.log_len, |s| s.offset);
+
+        Some(start..end)
+    }
+
+    /// index of the session containing byte at `offset`
+    pub fn session_at(&self, offset: u64) -> Option<usize> {
+        self.sessions.partition_point(|s| s.offset <= offset).checked_sub(1)
+    }
+
+    /// schema of the line starting at `offset`
+    pub fn schema_at(&self, offset: u64) -> LogSchema {
+        self.session_at(offset)
+            .map_or_else(LogSchema::default, |n| self.sessions[n].schema)
+    }
+
+    /// byte ranges of every fight, from `BEGIN_COMBAT` to the end of its `END_COMBAT` line,
+    /// fights that were never ended last until the next fight or session
+    pub fn fights(&self) -> Vec<Range<u64>> {
+        let mut fights = Vec::new();
+        let mut start = None;
+
+        for marker in &self.markers {
+            match marker.kind {
+                EventKind::BeginCombat => {
+                    if let Some(start) = start.replace(marker.offset) {
+                        fights.push(start..self.session_end(start).min(marker.offset));
+                    }
+                },
+                EventKind::EndCombat => {
+                    if let Some(start) = start.take() {
+                        fights.push(start..marker.end);
+                    }
+                },
+                _ => { },
+            }
+        }
+
+        if let Some(start) = start {
+            fights.push(start..self.session_end(start));
+        }
+
+        fights
+    }
+
+    /// byte range of the fight `n`, see `fights`
+    pub fn fight(&self, n: usize) -> Option<Range<u64>> {
+        self.fights().into_iter().nth(n)
+    }
+
+    /// byte range of the session `session`, that contains every line with timestamp in `from..=to`
+    ///
+    /// range starts and ends at checkpoints, so it may contain lines outside of the requested time
+    pub fn time_range(&self, session: usize, from: Duration, to: Duration) -> Option<Range<u64>> {
+        let session = self.session(session)?;
+        let checkpoints = {
+            let start = self.checkpoints.partition_point(|c| c.offset < session.start);
+            let end = self.checkpoints.partition_point(|c| c.offset < session.end);
+
+            &self.checkpoints[start..end]
+        };
+
+        // timestamps don't decrease within a session
+        let start = checkpoints.partition_point(|c| c.timestamp < from)
+            .checked_sub(1)
+            .map_or(session.start, |i| checkpoints[i].offset);
+        let end = checkpoints.iter()
+            .find(|c| c.timestamp > to)
+            .map_or(session.end, |c| c.offset);
+
+        Some(start..end)
+    }
+
+    /// read lines in `range` from the log
+    pub fn read_range<R: Read + Seek>(&self, mut reader: R, range: Range<u64>) -> io::Result<String> {
+        reader.seek(SeekFrom::Start(range.start))?;
+
+        let mut data = String::with_capacity(range.end.saturating_sub(range.start) as usize);
+        reader.take(range.end.saturating_sub(range.start)).read_to_string(&mut data)?;
+
+        Ok(data)
+    }
+
+    fn session_end(&self, offset: u64) -> u64 {
+        self.session_at(offset)
+            .and_then(|n| self.session(n))
+            .map_or(self.log_len, |session| session.end)
+    }
+
+    fn push_line(&mut self, offset: u64, line: &[u8]) {
+        let end = offset + line.len() as u64;
+
+        // name and timestamp are ASCII, lines that are not valid UTF-8 have neither of them
+        let Ok(line) = std::str::from_utf8(line) else {
+            return;
+        };
+
+        let line = line.trim_end_matches(['\n', '\r']);
+        let timestamp = line.split_once(',')
+            .and_then(|(timestamp, _)| timestamp.parse().ok())
+            .map(Duration::from_millis);
+        let Some(timestamp) = timestamp else {
+            return;
+        };
+
+        let checkpoint_due = self.checkpoints.last()
+            .map_or(offset >= CHECKPOINT_INTERVAL, |c| offset - c.offset >= CHECKPOINT_INTERVAL);
+        if checkpoint_due {
+            self.checkpoints.push(IndexCheckpoint { offset, timestamp });
+        }
+
+        let kind = match EventKind::from_name(line_event_name(line)) {
+            Some(EventKind::BeginLog) => {
+                let schema = Event::parse(line)
+                    .map_or_else(|_| LogSchema::default(), |e| LogSchema::default().after(&e));
+
+                self.sessions.push(IndexedSession { offset, schema });
+                // timestamps restart with every session
+                self.checkpoints.push(IndexCheckpoint { offset, timestamp });
+
+                return;
+            },
+            Some(kind @ (EventKind::BeginCombat | EventKind::EndCombat | EventKind::ZoneChanged | EventKind::BeginTrial)) => kind,
+            _ => return,
+        };
+
+        self.markers.push(IndexMarker {
+            kind,
+            offset,
+            end,
+            timestamp,
+        });
+    }
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn read_u8(reader: &mut impl Read) -> io::Result<u8> {
+    let mut buf = [0; 1];
+    reader.read_exact(&mut buf)?;
+
+    Ok(buf[0])
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut buf = [0; 4];
+    reader.read_exact(&mut buf)?;
+
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut buf = [0; 8];
+    reader.read_exact(&mut buf)?;
+
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_str(reader: &mut impl Read) -> io::Result<String> {
+    let mut buf = vec![0; read_u8(reader)? as usize];
+    reader.read_exact(&mut buf)?;
+
+    String::from_utf8(buf).map_err(|_| invalid_data("invalid string"))
+}
+
+fn write_str(writer: &mut impl Write, s: &str) -> io::Result<()> {
+    let len = u8::try_from(s.len()).map_err(|_| invalid_data("string too long"))?;
+    writer.write_all(&[len])?;
+    writer.write_all(s.as_bytes())
+}
+
+fn read_vec<R: Read, T>(reader: &mut R, mut read: impl FnMut(&mut R) -> io::Result<T>) -> io::Result<Vec<T>> {
+    let len = read_u64(reader)?;
+
+    (0..len).map(|_| read(reader)).collect()
+}
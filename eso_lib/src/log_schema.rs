@@ -0,0 +1,131 @@
+use std::str::FromStr;
+
+use rayon::prelude::*;
+
+use crate::{event_store::split_chunks, Event, EventBeginLog, EventType};
+
+/// Version of the game that wrote the log, eg. `eso.live.9.1.5.2919234`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, derive_more::Display)]
+#[display(fmt = "{}.{}.{}.{}", major, minor, patch, build)]
+pub struct GameVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub build: u32,
+}
+
+/// Field layout of the events, changes between game updates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum LogSchema {
+    /// logs written before U38 (9.0)
+    Legacy,
+    /// logs written since U38 (9.0)
+    #[default]
+    Current,
+}
+
+/// Schemas of the log sessions, by byte offset of their `BEGIN_LOG` line
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SchemaMap(Vec<(usize, LogSchema)>);
+
+impl GameVersion {
+    /// first version using `LogSchema::Current`
+    pub const U38: Self = Self::new(9, 0, 0, 0);
+
+    pub const fn new(major: u32, minor: u32, patch: u32, build: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            build,
+        }
+    }
+}
+
+impl FromStr for GameVersion {
+    type Err = std::num::ParseIntError;
+
+    /// parse version, skipping its non numeric prefix (eg. `eso.live.`),
+    /// missing trailing parts are treated as 0
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim_matches('"')
+            .split('.')
+            .skip_while(|part| !part.starts_with(|c: char| c.is_ascii_digit()));
+
+        let major = parts.next().unwrap_or_default().parse()?;
+        let mut next = || parts.next().map_or(Ok(0), str::parse);
+
+        Ok(Self::new(major, next()?, next()?, next()?))
+    }
+}
+
+impl LogSchema {
+    /// pick schema of the log session started by `e`,
+    /// `Current` if game version can't be determined
+    pub fn from_begin_log(e: &EventBeginLog) -> Self {
+        match e.parsed_game_version() {
+            Some(version) if version < GameVersion::U38 => Self::Legacy,
+            _ => Self::Current,
+        }
+    }
+
+    /// whether layout of the event `name` differs from the current one
+    #[inline]
+    pub(crate) fn is_changed(self, name: &str) -> bool {
+        match self {
+            Self::Legacy => matches!(name, "EFFECT_INFO"),
+            Self::Current => false,
+        }
+    }
+
+    /// schema that should be used for events following `e`
+    #[inline]
+    pub(crate) fn after(self, e: &Event) -> Self {
+        match e.event() {
+            EventType::BeginLog(e) => Self::from_begin_log(e),
+            _ => self,
+        }
+    }
+}
+
+impl SchemaMap {
+    /// find every `BEGIN_LOG` line of the log, and schema it selects
+    pub(crate) fn scan(s: &str) -> Self {
+        Self::scan_chunks(&split_chunks(s))
+    }
+
+    /// same as `scan`, for the log already split at line boundaries into `(offset, chunk)` pairs,
+    /// chunks are searched in parallel
+    pub(crate) fn scan_chunks(chunks: &[(usize, &str)]) -> Self {
+        let schemas = chunks.par_iter()
+            .flat_map_iter(|&(offset, chunk)| {
+                Self::scan_chunk(chunk).map(move |(start, schema)| (offset + start, schema))
+            })
+            .collect();
+
+        Self(schemas)
+    }
+
+    fn scan_chunk(s: &str) -> impl Iterator<Item = (usize, LogSchema)> + '_ {
+        s.match_indices(",BEGIN_LOG,")
+            .filter_map(|(i, _)| {
+                let start = s[..i].rfind('\n').map_or(0, |i| i + 1);
+                let end = s[i..].find('\n').map_or(s.len(), |end| i + end);
+
+                // `,BEGIN_LOG,` may also appear inside a quoted string of other event
+                match Event::parse(s[start..end].trim_end_matches('\r')).ok()?.event() {
+                    EventType::BeginLog(e) => Some((start, LogSchema::from_begin_log(e))),
+                    _ => None,
+                }
+            })
+    }
+
+    /// schema of the line starting at `offset`
+    #[inline]
+    pub(crate) fn get(&self, offset: usize) -> LogSchema {
+        match self.0.partition_point(|(start, _)| *start <= offset) {
+            0 => LogSchema::Current,
+            i => self.0[i - 1].1,
+        }
+    }
+}
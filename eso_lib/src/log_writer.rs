@@ -0,0 +1,70 @@
+use std::io::Write;
+
+use eso_parser::eso_serde::{SerializeError, Serializer};
+
+use crate::Event;
+
+/// Writes events as an encounter log, one event per line
+/// 
+/// every event is serialized straight into the writer, writes are small and frequent,
+/// so writer should be buffered, eg. with `BufWriter`.
+/// If writing an event fails, its line may be left incomplete
+/// 
+/// # Example usage
+/// ```no_run
+/// # use std::{fs::File, io::BufWriter};
+/// # use eso_lib::{Event, LogWriter};
+/// let data = std::fs::read_to_string("Encounter.log").unwrap();
+/// let mut writer = LogWriter::new(BufWriter::new(File::create("Filtered.log").unwrap()));
+/// 
+/// for event in Event::parse_many(&data) {
+///     let event = event.unwrap();
+/// 
+///     if event.event().combat_event().is_none() {
+///         writer.write(&event).unwrap();
+///     }
+/// }
+/// 
+/// writer.flush().unwrap();
+/// ```
+pub struct LogWriter<W: Write> {
+    serializer: Serializer<W>,
+}
+
+impl<W: Write> LogWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            serializer: Serializer::from_writer(writer),
+        }
+    }
+
+    /// write single event, terminated with a newline
+    pub fn write(&mut self, event: &Event) -> Result<(), SerializeError> {
+        event.serialize_line(&mut self.serializer)?;
+        self.serializer.end_line()
+    }
+
+    /// write every event from `events`, stopping at the first error
+    pub fn write_all<'a, 'e: 'a>(&mut self, events: impl IntoIterator<Item = &'a Event<'e>>) -> Result<(), SerializeError> {
+        events.into_iter().try_for_each(|event| self.write(event))
+    }
+
+    pub fn flush(&mut self) -> Result<(), SerializeError> {
+        self.serializer.writer_mut().flush()?;
+
+        Ok(())
+    }
+
+    pub fn get_ref(&self) -> &W {
+        self.serializer.writer()
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        self.serializer.writer_mut()
+    }
+
+    /// get back underlying writer, without flushing it
+    pub fn into_inner(self) -> W {
+        self.serializer.into_inner()
+    }
+}
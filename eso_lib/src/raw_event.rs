@@ -0,0 +1,124 @@
+use std::{borrow::Cow, fmt};
+
+use eso_parser::eso_serde::{newtypes::{EsoDuration, RawToken}, Error, ErrorKind, RawValue, SerializeError, Serializer};
+use serde::{de, Deserialize, Serialize};
+
+use crate::events::common::deserialize_cow_str;
+
+/// Any log line, with types of its fields inferred from their tokens
+/// 
+/// lets inspecting lines without typed struct, eg. events unknown to this version of the library
+/// 
+/// # Example usage
+/// ```
+/// # use eso_lib::RawEvent;
+/// let event = RawEvent::parse("10,NEW_EVENT,T,\"name\",[1,2],3/4,0.5").unwrap();
+/// 
+/// assert_eq!(event.name(), "NEW_EVENT");
+/// assert_eq!(event.fields().len(), 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RawEvent<'a> {
+    timestamp: EsoDuration,
+    name: Cow<'a, str>,
+    fields: Vec<RawValue<'a>>,
+}
+
+impl<'a> RawEvent<'a> {
+    /// parse any line, strings in returned event are borrowed from `s`
+    pub fn parse(s: &'a str) -> Result<Self, Error> {
+        let mut deserializer = eso_parser::eso_serde::Deserializer::new(s);
+
+        let event = deserializer.deserialize_line(RawEventVisitor)?;
+
+        if deserializer.is_depleted() {
+            Ok(event)
+        } else {
+            Err(ErrorKind::ReaderNotExhausted.into())
+        }
+    }
+
+    pub fn timestamp(&self) -> &EsoDuration {
+        &self.timestamp
+    }
+
+    /// name of the event, eg. `COMBAT_EVENT`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// every field following the event name
+    pub fn fields(&self) -> &[RawValue<'a>] {
+        &self.fields
+    }
+
+    /// Serializes this event to `String`
+    /// 
+    /// strings are always quoted and `a/b` pairs are written as lists, so the line
+    /// may differ from the parsed one, but it's parsed back into the same fields
+    pub fn dump(&self) -> Result<String, SerializeError> {
+        let mut serializer = Serializer::new();
+        self.serialize_line(&mut serializer)?;
+
+        Ok(serializer.into_string())
+    }
+
+    /// Serializes this event as a single line, without the line terminator, see `dump`
+    pub fn serialize_line<W: std::io::Write>(&self, serializer: &mut Serializer<W>) -> Result<(), SerializeError> {
+        self.timestamp.serialize(&mut *serializer)?;
+        RawToken(&self.name).serialize(&mut *serializer)?;
+        self.fields.iter().try_for_each(|field| field.serialize(&mut *serializer))
+    }
+
+    /// convert into event that doesn't borrow from the parsed data
+    pub fn into_owned(self) -> RawEvent<'static> {
+        RawEvent {
+            timestamp: self.timestamp,
+            name: Cow::Owned(self.name.into_owned()),
+            fields: self.fields.into_iter().map(RawValue::into_owned).collect(),
+        }
+    }
+}
+
+struct RawEventVisitor;
+
+/// event name, borrowed if deserializer allows it
+struct Name<'a>(Cow<'a, str>);
+
+impl<'de> Deserialize<'de> for Name<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_cow_str(deserializer).map(Self)
+    }
+}
+
+impl<'de> de::Visitor<'de> for RawEventVisitor {
+    type Value = RawEvent<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("timestamp, event name and its fields")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let timestamp = seq.next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let Name(name) = seq.next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        let mut fields = Vec::new();
+        while let Some(field) = seq.next_element()? {
+            fields.push(field);
+        }
+
+        Ok(RawEvent {
+            timestamp,
+            name,
+            fields,
+        })
+    }
+}
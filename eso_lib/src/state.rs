@@ -1,4 +1,4 @@
-use std::{collections::HashMap, borrow::Borrow};
+use std::{collections::HashMap, borrow::Borrow, time::Duration};
 
 use getset::Getters;
 
@@ -7,17 +7,34 @@ use crate::events::{*, common::*};
 /// Can be feeded events to save revelent informations
 /// like:
 /// - informations about abilities and effects
-/// - current units
+/// - current units, and units removed during the session
 /// - active effects
+/// - casts in progress
+/// - current zone, map and trial
 /// - and more
 #[derive(Debug, Clone, Getters)]
 #[getset(get = "pub")]
 pub struct State {
     entities: HashMap<UnitId, Unit>,
+    /// units removed during the session, in order of removal,
+    /// game reuses ids, so single id may refer to multiple units
+    removed_units: HashMap<UnitId, Vec<Unit>>,
     effects: EffectMap,
-    ability_info: AbilityInfoMap<EventAbilityInfo>,
+    casts: CastMap,
+    ability_info: AbilityInfoMap<EventAbilityInfo<'static>>,
     effect_info: AbilityInfoMap<EventEffectInfo>,
     in_combat: bool,
+    /// zone from the latest `ZONE_CHANGED`
+    zone: Option<EventZoneInfo<'static>>,
+    /// map from the latest `MAP_CHANGED`
+    map: Option<EventMapInfo<'static>>,
+    /// latest trial, kept after it ends
+    trial: Option<Trial>,
+    /// amount of events skipped because their type is unknown, by event name
+    unknown_events: HashMap<String, usize>,
+    /// amount of enum values unknown to this version of the library (parsed leniently as `Other`),
+    /// by enum name, then by the value
+    unknown_values: HashMap<&'static str, HashMap<String, usize>>,
 }
 
 /// holds informations about Unit
@@ -28,22 +45,105 @@ pub struct Unit {
     state: UnitState,
     reaction: UnitReactionType,
     equipment: HashMap<EquipSlot, EquipmentInfo>,
+    /// abilities on the front bar, from the latest `PLAYER_INFO`
+    primary_abilities: Vec<AbilityId>,
+    /// abilities on the back bar, from the latest `PLAYER_INFO`
+    backup_abilities: Vec<AbilityId>,
+    /// passives, CP and other long term effects, from the latest `PLAYER_INFO`
+    long_term_effects: Vec<LongTermEffect>,
+    /// every `PLAYER_INFO` received for this unit, in order
+    loadouts: Vec<Loadout>,
     name: String,
     display_name: String,
     monster_id: MonsterId,
     race_id: RaceId,
     class_id: ClassId,
     is_boss: bool,
+    /// unit controlling this one, eg. player summoning a pet or a companion
+    owner: Option<UnitId>,
+    /// time of the `UNIT_ADDED` event
+    added: Duration,
+    /// time of the removal, `None` while unit is present
+    removed: Option<Duration>,
+}
+
+/// abilities and long term effects of the player at the time of `PLAYER_INFO`
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct Loadout {
+    timestamp: Duration,
+    primary_abilities: Vec<AbilityId>,
+    backup_abilities: Vec<AbilityId>,
+    long_term_effects: Vec<LongTermEffect>,
 }
 
 // TODO: manual Debug impl
-// maybe FIXME: in game multiple units can share TrackId from single a source
 /// holds informations about active effects
 #[derive(Debug, Clone, Default)]
 pub struct EffectMap {
-    effects: HashMap<TrackId, EventEffectChanged>,
-    recevied_effects: HashMap<UnitId, Vec<TrackId>>,
-    granted_effects: HashMap<UnitId, Vec<TrackId>>,
+    effects: HashMap<EffectKey, EventEffectChanged>,
+    recevied_effects: HashMap<UnitId, Vec<EffectKey>>,
+    granted_effects: HashMap<UnitId, Vec<EffectKey>>,
+    /// targets of every `TrackId`, so effects don't have to be scanned by `get_by_id`
+    targets_by_id: HashMap<TrackId, Vec<UnitId>>,
+}
+
+/// identifies active effect, single cast can apply effects to multiple units
+/// under the same `TrackId`, eg. group buffs
+pub type EffectKey = (TrackId, UnitId);
+
+/// holds informations about trial, from `BEGIN_TRIAL`, `TRIAL_INIT` and `END_TRIAL`
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct Trial {
+    id: Id,
+    /// game time at which trial started
+    start_time: Duration,
+    /// set once trial is finished, or from `TRIAL_INIT`
+    duration: Duration,
+    in_progress: bool,
+    completed: bool,
+    success: bool,
+    score: Attribute,
+    /// only reported by `END_TRIAL`
+    vitality_bonus: Option<Attribute>,
+}
+
+/// holds informations about casts in progress, and finished ones
+#[derive(Debug, Clone, Default)]
+pub struct CastMap {
+    active: HashMap<TrackId, Cast>,
+    casting_units: HashMap<UnitId, Vec<TrackId>>,
+    /// casts that ended since the latest `BEGIN_COMBAT`, in order of their end
+    finished: Vec<Cast>,
+}
+
+/// single cast, started with `BEGIN_CAST`
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct Cast {
+    cast_id: TrackId,
+    ability_id: AbilityId,
+    source_unit: UnitId,
+    target_unit: UnitId,
+    /// expected duration of the cast
+    duration: Duration,
+    channeled: bool,
+    /// time of the `BEGIN_CAST` event
+    started: Duration,
+    /// `None` while cast is in progress
+    end: Option<CastEnd>,
+}
+
+/// how the cast ended, from `END_CAST`
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct CastEnd {
+    timestamp: Duration,
+    /// `None` if the cast didn't get its `END_CAST`, and was closed when its source unit was removed
+    reason: Option<EndCastReason>,
+    interrupting_ability_id: Option<AbilityId>,
+    interrupting_unit_id: Option<UnitId>,
 }
 
 /// holds informations about abilities and effects
@@ -55,10 +155,17 @@ impl State {
     pub fn new() -> Self {
         let mut this = Self {
             entities: Default::default(),
+            removed_units: Default::default(),
             effects: Default::default(),
+            casts: Default::default(),
             ability_info: Default::default(),
             effect_info: Default::default(),
             in_combat: Default::default(),
+            zone: Default::default(),
+            map: Default::default(),
+            trial: Default::default(),
+            unknown_events: Default::default(),
+            unknown_values: Default::default(),
         };
 
         let zero = UnitId(0);
@@ -68,12 +175,19 @@ impl State {
             state: UnitState::new(zero),
             reaction: UnitReactionType::Hostile,
             equipment: HashMap::new(),
+            primary_abilities: Vec::new(),
+            backup_abilities: Vec::new(),
+            long_term_effects: Vec::new(),
+            loadouts: Vec::new(),
             name: "World".into(),
             display_name: "World".into(), 
             monster_id: MonsterId(0), 
-            race_id: RaceId(0),
-            class_id: ClassId(0), 
+            race_id: RaceId::None,
+            class_id: ClassId::None, 
             is_boss: false,
+            owner: None,
+            added: Duration::ZERO,
+            removed: None,
         });
 
         this
@@ -83,18 +197,24 @@ impl State {
     pub fn handle_event(&mut self, e: &Event) {
         use EventType::*;
 
+        let timestamp = e.timestamp().0;
         // fix for vscode extension
         let e: &EventType = e.event();
 
+        for (name, value) in e.unknown_values() {
+            self.count_unknown_value(name, value);
+        }
+
         match e {
             AbilityInfo(v) => {
                 self.insert_ability_info(v);
             },
             BeginCombat(_) => {
+                self.casts.finished.clear();
                 self.in_combat = true;
             },
             BeginLog(_) => {
-                *self = Self::new();
+                self.reset();
             },
             CombatEvent(v) => {
                 self.update_unit_state(v.source_unit());
@@ -107,94 +227,200 @@ impl State {
                 self.insert_effect_info(v);
             },
             EndCombat(_) => {
-                self.remove_enemy_units();
+                self.remove_enemy_units(timestamp);
                 self.in_combat = false;
             },
             HealthRegen(v) => {
                 self.update_unit_state(v.unit());
             },
             PlayerInfo(v) => {
-                self.update_player(v);
+                self.update_player(v, timestamp);
             },
             UnitAdded(v) => {
-                self.add_unit(v);
+                self.add_unit(v, timestamp);
             },
             UnitChanged(v) => {
                 self.update_unit(v);
             },
             UnitRemoved(v) => {
-                self.remove_unit(v);
+                self.remove_unit(v.unit_id(), timestamp);
             },
             
-            BeginCast(_) => { /* noop */ },
-            BeginTrial(_) => { /* noop */ },
-            EndCast(_) => { /* noop */ },
+            BeginCast(v) => {
+                self.casts.handle_begin_cast(v, timestamp);
+            },
+            EndCast(v) => {
+                self.casts.handle_end_cast(v, timestamp);
+            },
+
+            BeginTrial(v) => {
+                self.begin_trial(v);
+            },
+            EndTrial(v) => {
+                self.end_trial(v);
+            },
+            MapChanged(v) => {
+                self.map = Some(v.clone().into_owned());
+            },
+            TrialInit(v) => {
+                self.init_trial(v);
+            },
+            ZoneChanged(v) => {
+                self.zone = Some(v.clone().into_owned());
+            },
+
             EndLog(_) => { /* noop */ },
-            EndTrial(_) => { /* noop */ },
-            MapChanged(_) => { /* noop */ },
-            TrialInit(_) => { /* noop */ },
-            ZoneChanged(_) => { /* noop */ },
+
+            Unknown { name, .. } => {
+                self.count_unknown_event(name);
+            },
         }
     }
 
+    /// start over with fresh instance, like on `BEGIN_LOG`,
+    /// skipped events are reported for the whole lifetime of the `State`, so they are kept
+    pub fn reset(&mut self) {
+        let unknown_events = std::mem::take(&mut self.unknown_events);
+        let unknown_values = std::mem::take(&mut self.unknown_values);
+        *self = Self::new();
+        self.unknown_events = unknown_events;
+        self.unknown_values = unknown_values;
+    }
+
     /// process multiple events
-    pub fn handle_events<Iter, T>(&mut self, e: Iter)
+    pub fn handle_events<'a, Iter, T>(&mut self, e: Iter)
     where
         Iter: IntoIterator<Item = T>,
-        T: Borrow<Event>,
+        T: Borrow<Event<'a>>,
     {
         e.into_iter()
          .for_each(|event| self.handle_event(event.borrow()));
     }
 
-    fn add_unit(&mut self, e: &EventUnitAdded) {
+    /// find unit with `unit_id`, present or already removed,
+    /// if id was reused, the most recent unit is returned
+    pub fn find_unit(&self, unit_id: &UnitId) -> Option<&Unit> {
+        self.entities
+            .get(unit_id)
+            .or_else(|| self.removed_units.get(unit_id)?.last())
+    }
+
+    /// find unit that had `unit_id` at `timestamp`, present or already removed
+    pub fn find_unit_at(&self, unit_id: &UnitId, timestamp: Duration) -> Option<&Unit> {
+        let present_at = |unit: &&Unit| unit.added <= timestamp && unit.removed.is_none_or(|removed| timestamp <= removed);
+
+        self.entities
+            .get(unit_id)
+            .filter(present_at)
+            .or_else(|| self.removed_units.get(unit_id)?.iter().rev().find(present_at))
+    }
+
+    /// id of the unit directly controlling `unit_id`, eg. player summoning a pet
+    pub fn owner_of(&self, unit_id: &UnitId) -> Option<UnitId> {
+        *self.find_unit(unit_id)?.owner()
+    }
+
+    /// id of the unit directly controlling `unit_id` at `timestamp`, see `find_unit_at`
+    pub fn owner_of_at(&self, unit_id: &UnitId, timestamp: Duration) -> Option<UnitId> {
+        *self.find_unit_at(unit_id, timestamp)?.owner()
+    }
+
+    /// player controlling `unit_id`, following chain of owners,
+    /// for players it's the unit itself
+    /// 
+    /// useful for crediting output of pets and companions to the player
+    pub fn controlling_player(&self, unit_id: &UnitId) -> Option<UnitId> {
+        self.follow_owners(unit_id, |unit_id| self.find_unit(unit_id))
+    }
+
+    /// player controlling `unit_id` at `timestamp`, every unit of the chain
+    /// is resolved at that time, see `find_unit_at`
+    pub fn controlling_player_at(&self, unit_id: &UnitId, timestamp: Duration) -> Option<UnitId> {
+        self.follow_owners(unit_id, |unit_id| self.find_unit_at(unit_id, timestamp))
+    }
+
+    fn follow_owners<'a>(&'a self, unit_id: &UnitId, find_unit: impl Fn(&UnitId) -> Option<&'a Unit>) -> Option<UnitId> {
+        let mut unit_id = *unit_id;
+
+        // bounded, in case of the ownership cycle
+        for _ in 0..MAX_OWNER_CHAIN {
+            let unit = find_unit(&unit_id)?;
+
+            if unit.unit_type == UnitType::Player {
+                return Some(unit_id);
+            }
+
+            unit_id = unit.owner?;
+        }
+
+        None
+    }
+
+    /// every unit seen in the session, both present and removed ones
+    pub fn all_units(&self) -> impl Iterator<Item = &Unit> {
         self.entities
+            .values()
+            .chain(self.removed_units.values().flatten())
+    }
+
+    fn add_unit(&mut self, e: &EventUnitAdded, timestamp: Duration) {
+        let replaced = self.entities
             .insert(*e.unit_id(), Unit {
                 unit_type: *e.unit_type(),
                 state: UnitState::new(*e.unit_id()),
                 reaction: *e.reaction(),
                 equipment: Default::default(),
-                name: e.name().clone(),
-                display_name: e.display_name().clone(),
+                primary_abilities: Default::default(),
+                backup_abilities: Default::default(),
+                long_term_effects: Default::default(),
+                loadouts: Default::default(),
+                name: e.name().to_owned(),
+                display_name: e.display_name().to_owned(),
                 monster_id: *e.monster_id(),
                 race_id: *e.race_id(),
                 class_id: *e.class_id(),
                 is_boss: *e.is_boss(),
+                owner: owner(*e.owner_id()),
+                added: timestamp,
+                removed: None,
             });
+
+        // id was reused without removing previous unit
+        if let Some(unit) = replaced {
+            self.archive_unit(unit, timestamp);
+        }
+    }
+
+    fn archive_unit(&mut self, mut unit: Unit, timestamp: Duration) {
+        unit.removed = Some(timestamp);
+
+        self.removed_units
+            .entry(*unit.state.unit_id())
+            .or_default()
+            .push(unit);
     }
 
     fn update_unit(&mut self, e: &EventUnitChanged) {
-        self.entities
-            .get_mut(e.unit_id())
-            .map(|unit| {
-                unit.reaction = *e.reaction();
-            });
+        if let Some(unit) = self.entities.get_mut(e.unit_id()) {
+            unit.reaction = *e.reaction();
+            unit.owner = owner(*e.owner_id());
+        }
     }
 
-    fn remove_unit(&mut self, e: &EventUnitRemoved) {
-        self.entities
-            .remove(e.unit_id());
+    fn remove_unit(&mut self, unit_id: &UnitId, timestamp: Duration) {
+        if let Some(unit) = self.entities.remove(unit_id) {
+            self.archive_unit(unit, timestamp);
+        }
 
-        let removed_effects = self.effects
-            .recevied_effects
-            .remove(e.unit_id());
+        // game doesn't always end casts of removed units
+        self.casts.remove_unit(unit_id, timestamp);
 
-        // iterate over `received_effects` and remove them, from `effects` and `granted_effects`
-        removed_effects.map(|v| v.iter().for_each(|track_id| {
-            self.effects
-                .effects
-                .remove(track_id)
-                .map(|effect_changed| {
-                    self.effects
-                        .granted_effects
-                        .remove(effect_changed.source_unit().unit_id());
-                });
-        }));
+        self.effects.remove_unit(unit_id);
     }
 
     // i can't determine if this should be called
     // and if on combat end or combat begin
-    fn remove_enemy_units(&mut self) {
+    fn remove_enemy_units(&mut self, timestamp: Duration) {
         let hostile_units: Vec<_> = self.entities()
             .iter()
             .filter_map(|(id, unit)| {
@@ -204,44 +430,138 @@ impl State {
 
         hostile_units.into_iter()
             .for_each(|unit_id| {
-                self.remove_unit(&EventUnitRemoved { unit_id })
+                self.remove_unit(&unit_id, timestamp)
             });
     }
 
-    fn update_player(&mut self, e: &EventPlayerInfo) {
-        self.entities
-            .get_mut(e.unit_id())
-            .map(|unit| {
-                e.equipment_info()
-                 .iter()
-                 .for_each(|eq| {
-                    unit.equipment
-                        .insert(*eq.slot(), eq.clone());
-                    
-                    // TODO: add ability bars and long term effects
-                 });
+    fn update_player(&mut self, e: &EventPlayerInfo, timestamp: Duration) {
+        if let Some(unit) = self.entities.get_mut(e.unit_id()) {
+            e.equipment_info()
+             .iter()
+             .for_each(|eq| {
+                unit.equipment
+                    .insert(*eq.slot(), eq.clone());
+             });
+
+            unit.primary_abilities = e.primary_abilities().clone();
+            unit.backup_abilities = e.backup_abilities().clone();
+            unit.long_term_effects = e.long_term_effects().clone();
+
+            unit.loadouts.push(Loadout {
+                timestamp,
+                primary_abilities: e.primary_abilities().clone(),
+                backup_abilities: e.backup_abilities().clone(),
+                long_term_effects: e.long_term_effects().clone(),
             });
+        }
     }
 
     fn update_unit_state(&mut self, unit_state: &UnitState) {
-        self.entities
-            .get_mut(unit_state.unit_id())
-            .map(|unit| {
-                unit.state = unit_state.clone();
+        if let Some(unit) = self.entities.get_mut(unit_state.unit_id()) {
+            unit.state = unit_state.clone();
+        }
+    }
+
+    fn begin_trial(&mut self, e: &EventBeginTrial) {
+        self.trial = Some(Trial {
+            id: *e.id(),
+            start_time: e.start_time().0,
+            duration: Duration::ZERO,
+            in_progress: true,
+            completed: false,
+            success: false,
+            score: 0,
+            vitality_bonus: None,
+        });
+    }
+
+    fn init_trial(&mut self, e: &EventTrialInit) {
+        self.trial = Some(Trial {
+            id: *e.id(),
+            start_time: e.start_time().0,
+            duration: e.duration().0,
+            in_progress: *e.in_progress(),
+            completed: *e.completed(),
+            success: *e.success(),
+            score: *e.final_score(),
+            vitality_bonus: None,
+        });
+    }
+
+    fn end_trial(&mut self, e: &EventEndTrial) {
+        // trial might have started before logging
+        let trial = self.trial
+            .get_or_insert_with(|| Trial {
+                id: *e.id(),
+                start_time: Duration::ZERO,
+                duration: Duration::ZERO,
+                in_progress: false,
+                completed: false,
+                success: false,
+                score: 0,
+                vitality_bonus: None,
             });
+
+        trial.id = *e.id();
+        trial.duration = e.duration().0;
+        trial.in_progress = false;
+        trial.completed = true;
+        trial.success = *e.success();
+        trial.score = *e.final_score();
+        trial.vitality_bonus = Some(*e.final_vitality_bonus());
+    }
+
+    fn count_unknown_event(&mut self, name: &str) {
+        match self.unknown_events.get_mut(name) {
+            Some(count) => *count += 1,
+            None => {
+                self.unknown_events.insert(name.to_owned(), 1);
+            },
+        }
+    }
+
+    fn count_unknown_value(&mut self, name: &'static str, value: &str) {
+        let values = self.unknown_values.entry(name).or_default();
+
+        match values.get_mut(value) {
+            Some(count) => *count += 1,
+            None => {
+                values.insert(value.to_owned(), 1);
+            },
+        }
     }
 
     #[inline]
     fn insert_ability_info(&mut self, e: &EventAbilityInfo) {
-        self.ability_info.insert(*e.ability_id(), e);
+        self.ability_info.insert_with(*e.ability_id(), || e.clone().into_owned());
     }
 
     #[inline]
     fn insert_effect_info(&mut self, e: &EventEffectInfo) {
-        self.effect_info.insert(*e.ability_id(), e);
+        self.effect_info.insert_with(*e.ability_id(), || e.clone());
+    }
+}
+
+impl Unit {
+    /// loadout that unit used at `timestamp`, eg. at the start of a fight,
+    /// that is the latest one received before it
+    pub fn loadout_at(&self, timestamp: Duration) -> Option<&Loadout> {
+        self.loadouts
+            .iter()
+            .rev()
+            .find(|loadout| loadout.timestamp <= timestamp)
     }
 }
 
+/// owners of the owners are followed at most this many times
+const MAX_OWNER_CHAIN: usize = 8;
+
+/// owner id `0` means that unit has no owner
+#[inline]
+fn owner(owner_id: UnitId) -> Option<UnitId> {
+    (owner_id != UnitId(0)).then_some(owner_id)
+}
+
 impl EffectMap {
     /// process EffectChanged event
     pub fn handle_effect_changed(&mut self, e: &EventEffectChanged) {
@@ -254,34 +574,46 @@ impl EffectMap {
                 self.insert(e.clone());
             },
             EffectChangeType::Faded => {
-                self.remove(e.cast_id());
+                self.remove(&(*e.cast_id(), *e.target_unit().unit_id()));
             }
         }
     }
 
-    fn remove(&mut self, id: &TrackId) {
-        self.effects
-            .remove(&id)
-            .map(|effect| {
-                self.granted_effects
-                    .get_mut(effect.source_unit().unit_id())
-                    .map(|v| v.retain(|tid| tid != id));
+    fn remove(&mut self, key: &EffectKey) {
+        if let Some(effect) = self.effects.remove(key) {
+            if let Some(v) = self.granted_effects.get_mut(effect.source_unit().unit_id()) {
+                v.retain(|k| k != key);
+            }
 
-                self.recevied_effects
-                    .get_mut(effect.target_unit().unit_id())
-                    .map(|v| v.retain(|tid| tid != id));
+            if let Some(v) = self.recevied_effects.get_mut(effect.target_unit().unit_id()) {
+                v.retain(|k| k != key);
+            }
 
-                effect
-            });
+            let (track_id, target_unit) = key;
+            if let Some(v) = self.targets_by_id.get_mut(track_id) {
+                v.retain(|unit_id| unit_id != target_unit);
+
+                if v.is_empty() {
+                    self.targets_by_id.remove(track_id);
+                }
+            }
+        }
+    }
+
+    /// remove every effect received by `unit_id`
+    fn remove_unit(&mut self, unit_id: &UnitId) {
+        for key in self.recevied_effects.remove(unit_id).unwrap_or_default() {
+            self.remove(&key);
+        }
     }
 
     fn insert(&mut self, e: EventEffectChanged) {
-        let cast_id = *e.cast_id();
         let source_unit = *e.source_unit().unit_id();
         let target_unit = *e.target_unit().unit_id();
+        let key = (*e.cast_id(), target_unit);
 
         let option = self.effects
-            .insert(cast_id, e);
+            .insert(key, e);
 
         match option {
             Some(_v) => {
@@ -294,45 +626,171 @@ impl EffectMap {
                 self.granted_effects
                     .entry(source_unit)
                     .or_default()
-                    .push(cast_id);
+                    .push(key);
 
                 self.recevied_effects
                     .entry(target_unit)
                     .or_default()
-                    .push(cast_id);
+                    .push(key);
+
+                self.targets_by_id
+                    .entry(key.0)
+                    .or_default()
+                    .push(target_unit);
             }
         }
     }
 
-    /// get effect by its `TrackId`
-    pub fn get_by_id(&self, track_id: &TrackId) -> Option<&EventEffectChanged> {
-        self.effects.get(track_id)
+    /// get effect by its `TrackId` and target unit
+    pub fn get(&self, key: &EffectKey) -> Option<&EventEffectChanged> {
+        self.effects.get(key)
+    }
+
+    /// get effects applied with `TrackId`, on every target
+    pub fn get_by_id<'a>(&'a self, track_id: &'a TrackId) -> impl Iterator<Item = &'a EventEffectChanged> {
+        self.targets_by_id
+            .get(track_id)
+            .into_iter()
+            .flatten()
+            .filter_map(move |target_unit| self.effects.get(&(*track_id, *target_unit)))
     }
 
     /// get all effects that unit granted (possible to other units)
-    pub fn get_granted_effects(&self, unit_id: &UnitId) -> Option<&[TrackId]> {
+    pub fn get_granted_effects(&self, unit_id: &UnitId) -> Option<&[EffectKey]> {
         self.granted_effects.get(unit_id).map(Vec::as_slice)
     }
 
     /// get all effects that unit has received
-    pub fn get_received_effects(&self, unit_id: &UnitId) -> Option<&[TrackId]> {
+    pub fn get_received_effects(&self, unit_id: &UnitId) -> Option<&[EffectKey]> {
         self.recevied_effects.get(unit_id).map(Vec::as_slice)
     }
 
     /// get all active effects
-    pub fn effects(&self) -> &HashMap<TrackId, EventEffectChanged> {
+    pub fn effects(&self) -> &HashMap<EffectKey, EventEffectChanged> {
         &self.effects
     }
 }
 
-impl<T> AbilityInfoMap<T>
-where
-    T: Clone,
-{
-    fn insert(&mut self, id: AbilityId, info: &T) {
+impl CastMap {
+    /// process BeginCast event
+    pub fn handle_begin_cast(&mut self, e: &EventBeginCast, timestamp: Duration) {
+        let cast = Cast {
+            cast_id: *e.cast_id(),
+            ability_id: *e.ability_id(),
+            source_unit: *e.source_unit().unit_id(),
+            target_unit: *e.target_unit().unit_id(),
+            duration: e.duration().0,
+            channeled: *e.channeled(),
+            started: timestamp,
+            end: None,
+        };
+
+        if let Some(replaced) = self.active.insert(cast.cast_id, cast) {
+            self.unlink(&replaced);
+        }
+
+        self.casting_units
+            .entry(*e.source_unit().unit_id())
+            .or_default()
+            .push(*e.cast_id());
+    }
+
+    /// process EndCast event
+    pub fn handle_end_cast(&mut self, e: &EventEndCast, timestamp: Duration) {
+        if let Some(mut cast) = self.active.remove(e.cast_id()) {
+            self.unlink(&cast);
+
+            cast.end = Some(CastEnd {
+                timestamp,
+                reason: Some(e.reason().clone()),
+                interrupting_ability_id: e.interrupting_ability_id().copied(),
+                interrupting_unit_id: e.interrupting_unit_id().copied(),
+            });
+
+            self.finished.push(cast);
+        }
+    }
+
+    fn remove_unit(&mut self, unit_id: &UnitId, timestamp: Duration) {
+        if let Some(v) = self.casting_units.remove(unit_id) {
+            v.iter().for_each(|cast_id| {
+                if let Some(mut cast) = self.active.remove(cast_id) {
+                    cast.end = Some(CastEnd {
+                        timestamp,
+                        reason: None,
+                        interrupting_ability_id: None,
+                        interrupting_unit_id: None,
+                    });
+
+                    self.finished.push(cast);
+                }
+            });
+        }
+    }
+
+    fn unlink(&mut self, cast: &Cast) {
+        if let Some(v) = self.casting_units.get_mut(&cast.source_unit) {
+            v.retain(|cast_id| cast_id != &cast.cast_id);
+        }
+    }
+
+    /// get cast in progress by its `TrackId`
+    pub fn get_by_id(&self, cast_id: &TrackId) -> Option<&Cast> {
+        self.active.get(cast_id)
+    }
+
+    /// get all casts in progress, started by the unit
+    pub fn get_unit_casts(&self, unit_id: &UnitId) -> impl Iterator<Item = &Cast> {
+        self.casting_units
+            .get(unit_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|cast_id| self.active.get(cast_id))
+    }
+
+    /// get the most recent cast, that unit is casting at `timestamp`,
+    /// casts past their expected end are skipped
+    pub fn current_cast(&self, unit_id: &UnitId, timestamp: Duration) -> Option<&Cast> {
+        self.get_unit_casts(unit_id)
+            .filter(|cast| cast.started <= timestamp && timestamp <= cast.expected_end())
+            .last()
+    }
+
+    /// get all casts in progress
+    pub fn active(&self) -> &HashMap<TrackId, Cast> {
+        &self.active
+    }
+
+    /// get casts that ended since the latest `BEGIN_COMBAT`, in order of their end,
+    /// older ones are dropped to keep memory bounded over long sessions
+    pub fn finished(&self) -> &[Cast] {
+        &self.finished
+    }
+}
+
+impl Cast {
+    /// time at which cast should finish, if not interrupted
+    pub fn expected_end(&self) -> Duration {
+        self.started + self.duration
+    }
+
+    /// time left until the cast finishes, zero if it's already past expected end
+    pub fn remaining(&self, timestamp: Duration) -> Duration {
+        self.expected_end().saturating_sub(timestamp)
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> AbilityInfoMap<T> {
+    fn insert_with<F: FnOnce() -> T>(&mut self, id: AbilityId, info: F) {
         self.0
             .entry(id)
-            .or_insert_with(|| info.clone());
+            .or_insert_with(info);
     }
 
     /// retrieve informations about ability/effect
@@ -0,0 +1,83 @@
+//! fixtures shared by the integration tests, every test uses only some of them
+
+#![allow(dead_code, unused_macros)]
+
+use std::{fs, path::{Path, PathBuf}};
+
+/// `BEGIN_LOG` line of the current game version, log started at unix time `$time` (in milliseconds)
+macro_rules! begin_log {
+    () => {
+        begin_log!(1700000000000)
+    };
+    ($time:literal) => {
+        concat!("0,BEGIN_LOG,", $time, r#",15,"NA Megaserver","en","eso.live.9.1.5.2919234""#)
+    };
+}
+
+/// `BEGIN_LOG` line of the game version using legacy event layouts, see `begin_log!`
+macro_rules! legacy_begin_log {
+    () => {
+        legacy_begin_log!(1600000000000)
+    };
+    ($time:literal) => {
+        concat!("0,BEGIN_LOG,", $time, r#",15,"NA Megaserver","en","eso.live.8.3.5.2800000""#)
+    };
+}
+
+pub const BEGIN_LOG: &str = begin_log!();
+pub const LEGACY_BEGIN_LOG: &str = legacy_begin_log!();
+pub const BEGIN_COMBAT: &str = "9,BEGIN_COMBAT";
+
+pub fn unit_added(name: &str) -> String {
+    format!(r#"3,UNIT_ADDED,1,PLAYER,T,1,0,F,6,7,"{name}","@account",123456789,50,1800,0,PLAYER_ALLY,T"#)
+}
+
+/// log exactly `len` bytes long, its last line is `UNIT_ADDED` with name padded to fit
+pub fn log_of_len(len: usize, terminated: bool) -> String {
+    let tail_len = unit_added("").len() + terminated as usize;
+
+    let mut log = format!("{BEGIN_LOG}\n");
+    while len - log.len() > tail_len + BEGIN_COMBAT.len() + 1 {
+        log.push_str(BEGIN_COMBAT);
+        log.push('\n');
+    }
+
+    log.push_str(&unit_added(&"x".repeat(len - log.len() - tail_len)));
+    if terminated {
+        log.push('\n');
+    }
+
+    assert_eq!(log.len(), len);
+    log
+}
+
+/// directory in the system temp dir, removed on drop
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// `name` must be unique within the test target
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("{}_{}_{name}", env!("CARGO_CRATE_NAME"), std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        Self(dir)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// write `data` into `file` inside of this directory
+    pub fn write(&self, file: &str, data: &str) -> PathBuf {
+        let path = self.0.join(file);
+        fs::write(&path, data).unwrap();
+
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
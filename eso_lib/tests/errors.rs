@@ -0,0 +1,77 @@
+#[macro_use]
+mod common;
+
+use eso_lib::{eso_parser::eso_serde::Error, Event};
+use rayon::prelude::ParallelIterator;
+
+const LOG: &str = concat!(begin_log!(), r#"
+9,BEGIN_COMBAT
+3,UNIT_ADDED,1,PLAYER,T,x1,0,F,6,7,"Player Name","@account",123456789,50,1800,0,PLAYER_ALLY,T
+17,END_COMBAT
+18,UNIT_REMOVED,2
+4,UNIT_ADDED,2,MONSTER,F,0,12345,T,0,0,"Z'Maja","",0,50,160,0,HOSTYLE,F
+19,END_LOG"#);
+
+/// (line, offset, event, field index, field name, token) of the error
+type Context<'a> = (Option<usize>, Option<u64>, Option<&'a str>, Option<usize>, Option<&'static str>, Option<&'a str>);
+
+fn context(e: &Error) -> Context<'_> {
+    (e.line(), e.offset(), e.event(), e.field_index(), e.field_name(), e.token())
+}
+
+fn expected() -> [Context<'static>; 2] {
+    let offset = |line: usize| LOG.lines().take(line - 1).map(|l| l.len() as u64 + 1).sum();
+
+    [
+        (Some(3), Some(offset(3)), Some("UNIT_ADDED"), Some(4), Some("player_per_session_id"), Some("x1")),
+        (Some(6), Some(offset(6)), Some("UNIT_ADDED"), Some(15), Some("reaction"), Some("HOSTYLE")),
+    ]
+}
+
+#[test]
+fn errors_have_context() {
+    let errors: Vec<_> = Event::parse_many(&LOG)
+        .filter_map(Result::err)
+        .collect();
+
+    let contexts: Vec<_> = errors.iter().map(context).collect();
+    assert_eq!(contexts, expected());
+
+    assert_eq!(
+        errors[0].to_string(),
+        "line 3 (byte 90): UNIT_ADDED field 4 player_per_session_id: ParseIntError (token `x1`)",
+    );
+}
+
+#[test]
+fn parallel_errors_have_context() {
+    let mut errors: Vec<_> = Event::parse_many_par(&LOG)
+        .filter_map(Result::err)
+        .collect();
+    errors.sort_by_key(|e| e.line());
+
+    let contexts: Vec<_> = errors.iter().map(context).collect();
+    assert_eq!(contexts, expected());
+}
+
+#[test]
+fn parallel_line_numbers_match_sequential() {
+    // many bad lines, spread over the whole log
+    let log: String = (0..2000)
+        .map(|i| if i % 7 == 3 { "9,BEGIN_COMBAT,x\n" } else { "9,BEGIN_COMBAT\n" })
+        .collect();
+
+    let sequential: Vec<_> = Event::parse_many(&log)
+        .filter_map(Result::err)
+        .map(|e| (e.line(), e.offset()))
+        .collect();
+
+    let mut parallel: Vec<_> = Event::parse_many_par(&log)
+        .filter_map(Result::err)
+        .map(|e| (e.line(), e.offset()))
+        .collect();
+    parallel.sort();
+
+    assert_eq!(sequential.len(), 2000 / 7 + 1);
+    assert_eq!(parallel, sequential);
+}
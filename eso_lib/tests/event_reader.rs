@@ -0,0 +1,87 @@
+#[macro_use]
+mod common;
+
+use std::io::Cursor;
+
+use eso_lib::{eso_parser::eso_serde::ErrorKind, Event, EventKind, EventReader};
+
+const LOG: &str = concat!(begin_log!(), r#"
+3,UNIT_ADDED,1,PLAYER,T,1,0,F,6,7,"Player Name","@account",123456789,50,1800,0,PLAYER_ALLY,T
+8,PLAYER_INFO,1,[142210,142079],[1,1],[[HEAD,94779,T,16,ARMOR_DIVINES,LEGENDARY,640,INVALID,F,0,NORMAL],[CHEST,94780,T,16,ARMOR_DIVINES,LEGENDARY,640,MAGICKA,T,16,LEGENDARY]],[46331,24328,22259,24330,24165,86019],[23234,61919,24326,39012,86015,86011]
+9,BEGIN_COMBAT
+17,END_COMBAT
+23,END_LOG"#);
+
+fn dump_all(data: &[u8], chunk_size: usize) -> Vec<String> {
+    EventReader::with_chunk_size(Cursor::new(data), chunk_size)
+        .map(|e| e.unwrap().dump().unwrap())
+        .collect()
+}
+
+#[test]
+fn lines_split_across_chunks() {
+    let expected: Vec<_> = LOG.lines().collect();
+
+    // every chunk size up to the longest line, so lines are split at every possible place
+    let longest = LOG.lines().map(str::len).max().unwrap();
+    for chunk_size in 1..=longest + 1 {
+        assert_eq!(dump_all(LOG.as_bytes(), chunk_size), expected, "chunk size {chunk_size}");
+    }
+}
+
+#[test]
+fn lines_longer_than_chunk_grow_the_buffer() {
+    let line = LOG.lines().find(|line| line.contains(",PLAYER_INFO,")).unwrap();
+    let data = format!("{line}\n{line}\n");
+
+    assert_eq!(dump_all(data.as_bytes(), 4), [line, line]);
+}
+
+#[test]
+fn crlf_terminators_are_stripped() {
+    let expected: Vec<_> = LOG.lines().collect();
+    let data = LOG.replace('\n', "\r\n");
+
+    assert_eq!(dump_all(data.as_bytes(), 7), expected);
+    assert_eq!(dump_all(data.as_bytes(), 1 << 20), expected);
+
+    // unterminated last line, that still has its `\r`
+    let data = data + "\r";
+    assert_eq!(dump_all(data.as_bytes(), 7), expected);
+}
+
+#[test]
+fn unterminated_last_line_is_parsed() {
+    let events: Vec<_> = Event::parse_reader(Cursor::new(LOG))
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(events.len(), LOG.lines().count());
+    assert_eq!(events.last().unwrap().kind(), EventKind::EndLog);
+
+    // terminated one doesn't produce an extra event
+    let data = format!("{LOG}\n");
+    assert_eq!(Event::parse_reader(Cursor::new(data)).count(), events.len());
+}
+
+#[test]
+fn invalid_utf8_fails_only_its_line() {
+    let mut data = Vec::new();
+    data.extend_from_slice(b"9,BEGIN_COMBAT\n");
+    data.extend_from_slice(b"3,UNIT_ADDED,1,PLAYER,T,1,0,F,6,7,\"Name \xff\",\"@account\",123456789,50,1800,0,PLAYER_ALLY,T\n");
+    data.extend_from_slice(b"17,END_COMBAT");
+
+    for chunk_size in [1, 5, 1 << 20] {
+        let events: Vec<_> = EventReader::with_chunk_size(Cursor::new(&data), chunk_size).collect();
+        assert_eq!(events.len(), 3);
+
+        assert_eq!(events[0].as_ref().unwrap().kind(), EventKind::BeginCombat);
+
+        let error = events[1].as_ref().unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::Utf8Error(_)), "{error}");
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.offset(), Some(15));
+
+        assert_eq!(events[2].as_ref().unwrap().kind(), EventKind::EndCombat);
+    }
+}
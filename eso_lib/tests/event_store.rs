@@ -0,0 +1,114 @@
+mod common;
+
+use common::{log_of_len, BEGIN_COMBAT, BEGIN_LOG, LEGACY_BEGIN_LOG};
+use eso_lib::{DisplayBehaviour, Event, EventStore, EventType};
+use rayon::prelude::ParallelIterator;
+
+const END_COMBAT: &str = "17,END_COMBAT";
+const LEGACY_EFFECT_INFO: &str = "6,EFFECT_INFO,61771,BUFF,NONE,T";
+
+/// same as `CHUNK_SIZE` of `EventStore`
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// sessions split by parsing the log sequentially
+fn expected_sessions(log: &str) -> Vec<Vec<String>> {
+    let mut sessions: Vec<Vec<String>> = Vec::new();
+
+    for event in Event::parse_many(&log) {
+        let event = event.unwrap();
+
+        if sessions.is_empty() || matches!(event.event(), EventType::BeginLog(_)) {
+            sessions.push(Vec::new());
+        }
+        sessions.last_mut().unwrap().push(event.dump().unwrap());
+    }
+
+    sessions
+}
+
+fn sessions(store: &EventStore) -> Vec<Vec<String>> {
+    store.sessions()
+        .map(|session| session.iter().map(|e| e.dump().unwrap()).collect())
+        .collect()
+}
+
+#[test]
+fn leading_events_form_own_session() {
+    let log = format!("{BEGIN_COMBAT}\n{END_COMBAT}\n{BEGIN_LOG}\n{BEGIN_COMBAT}\n");
+    let store = EventStore::parse(&log).unwrap();
+
+    assert_eq!(store.session_starts(), [2]);
+    assert_eq!(sessions(&store), [vec![BEGIN_COMBAT, END_COMBAT], vec![BEGIN_LOG, BEGIN_COMBAT]]);
+
+    // no `BEGIN_LOG` at all
+    let log = format!("{BEGIN_COMBAT}\n{END_COMBAT}");
+    let store = EventStore::parse(&log).unwrap();
+
+    assert!(store.session_starts().is_empty());
+    assert_eq!(sessions(&store), [[BEGIN_COMBAT, END_COMBAT]]);
+
+    // nothing to split
+    let store = EventStore::parse(&"").unwrap();
+    assert_eq!(store.sessions().count(), 0);
+}
+
+#[test]
+fn several_sessions() {
+    // second session has only its `BEGIN_LOG`
+    let log = format!("{BEGIN_LOG}\n{BEGIN_COMBAT}\n{END_COMBAT}\n{LEGACY_BEGIN_LOG}\n{BEGIN_LOG}\n{BEGIN_COMBAT}");
+    let store = EventStore::parse(&log).unwrap();
+
+    assert_eq!(store.session_starts(), [0, 3, 4]);
+    assert_eq!(sessions(&store), [
+        vec![BEGIN_LOG, BEGIN_COMBAT, END_COMBAT],
+        vec![LEGACY_BEGIN_LOG],
+        vec![BEGIN_LOG, BEGIN_COMBAT],
+    ]);
+    assert_eq!(sessions(&store), expected_sessions(&log));
+}
+
+/// log, where `BEGIN_LOG` of the legacy session starts at byte `start`,
+/// followed by legacy `EFFECT_INFO` lines in the next chunk
+fn log_with_session_at(start: usize) -> String {
+    let mut log = log_of_len(start, true);
+    log.push_str(LEGACY_BEGIN_LOG);
+    log.push('\n');
+
+    while log.len() < CHUNK_SIZE + 2 * LEGACY_BEGIN_LOG.len() {
+        log.push_str(LEGACY_EFFECT_INFO);
+        log.push('\n');
+    }
+    log.push_str(END_COMBAT);
+
+    log
+}
+
+#[test]
+fn sessions_across_chunk_boundaries() {
+    let begin_log = LEGACY_BEGIN_LOG.len() + 1;
+
+    // `BEGIN_LOG` ending the first chunk, containing its boundary, and starting the second chunk
+    for start in [CHUNK_SIZE - begin_log, CHUNK_SIZE - 1, CHUNK_SIZE, CHUNK_SIZE + 1] {
+        let log = log_with_session_at(start);
+        let store = EventStore::parse(&log).unwrap();
+
+        let expected = expected_sessions(&log);
+        assert_eq!(expected.len(), 2);
+        assert_eq!(sessions(&store), expected, "session starting at {start}");
+
+        // every `EFFECT_INFO` after the boundary uses legacy layout
+        let legacy = store.sessions()
+            .nth(1)
+            .unwrap()
+            .iter()
+            .filter_map(|e| e.event().effect_info())
+            .all(|e| *e.effect_bar_display_behaviour() == DisplayBehaviour::Never);
+        assert!(legacy);
+
+        // schemas of parallel parsing are also found per chunk
+        let parallel: Vec<_> = Event::parse_many_par(&log)
+            .map(|e| e.unwrap().dump().unwrap())
+            .collect();
+        assert_eq!(parallel, expected.concat());
+    }
+}
@@ -0,0 +1,79 @@
+use eso_lib::{events::common::{ClassId, PowerType, RaceId}, Event};
+
+fn combat_event(power_type: u64) -> String {
+    format!("11,COMBAT_EVENT,DAMAGE,PHYSICAL,{power_type},12345,0,1234,61771,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,*")
+}
+
+fn unit_added(class_id: u64, race_id: u64) -> String {
+    format!(r#"3,UNIT_ADDED,1,PLAYER,T,1,0,F,{class_id},{race_id},"Player Name","@account",123456789,50,1800,0,PLAYER_ALLY,T"#)
+}
+
+/// every known value is read back from its id, in the log and in JSON
+macro_rules! assert_round_trip {
+    ($t:ty, $parse:expr) => {
+        for &value in <$t>::ALL {
+            assert_eq!(<$t>::from_id(value.get()), value);
+            assert_eq!($parse(value.get()), value);
+
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(json, value.get().to_string());
+            assert_eq!(serde_json::from_str::<$t>(&json).unwrap(), value);
+        }
+    };
+}
+
+fn parse_power_type(id: u64) -> PowerType {
+    let line = combat_event(id);
+    let event = Event::parse(&line).unwrap();
+    assert_eq!(event.dump().unwrap(), line);
+
+    *event.event().combat_event().unwrap().power_type()
+}
+
+fn parse_class_id(id: u64) -> ClassId {
+    let line = unit_added(id, 0);
+    let event = Event::parse(&line).unwrap();
+    assert_eq!(event.dump().unwrap(), line);
+
+    *event.event().unit_added().unwrap().class_id()
+}
+
+fn parse_race_id(id: u64) -> RaceId {
+    let line = unit_added(0, id);
+    let event = Event::parse(&line).unwrap();
+    assert_eq!(event.dump().unwrap(), line);
+
+    *event.event().unit_added().unwrap().race_id()
+}
+
+#[test]
+fn known_ids_round_trip() {
+    assert_round_trip!(PowerType, parse_power_type);
+    assert_round_trip!(ClassId, parse_class_id);
+    assert_round_trip!(RaceId, parse_race_id);
+
+    assert_eq!(PowerType::ALL.len(), 9);
+    assert_eq!(PowerType::HealthBonus.get(), 128);
+    assert_eq!(ClassId::Arcanist.get(), 117);
+    assert_eq!(RaceId::Imperial.get(), 10);
+}
+
+#[test]
+fn unknown_ids_are_kept() {
+    assert_eq!(parse_power_type(256), PowerType::Unknown(256));
+    assert_eq!(parse_power_type(u64::MAX), PowerType::Unknown(u64::MAX));
+    assert_eq!(parse_class_id(8), ClassId::Unknown(8));
+    assert_eq!(parse_race_id(11), RaceId::Unknown(11));
+
+    assert_eq!(PowerType::Unknown(256).get(), 256);
+    assert_eq!(serde_json::to_string(&ClassId::Unknown(8)).unwrap(), "8");
+    assert_eq!(serde_json::from_str::<RaceId>("11").unwrap(), RaceId::Unknown(11));
+}
+
+#[test]
+fn display_names() {
+    assert_eq!(PowerType::MountStamina.to_string(), "Mount Stamina");
+    assert_eq!(PowerType::HealthBonus.to_string(), "Health Bonus");
+    assert_eq!(ClassId::Dragonknight.to_string(), "Dragonknight");
+    assert_eq!(RaceId::Unknown(11).to_string(), "Unknown (11)");
+}
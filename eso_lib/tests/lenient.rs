@@ -0,0 +1,84 @@
+use eso_lib::{events::EventType, ActionResult, Event, State};
+use rayon::prelude::ParallelIterator;
+
+const UNKNOWN_EVENT: &str = r#"5,NEW_EVENT,1,[2,3],"Some, name",T"#;
+const UNKNOWN_VALUE: &str = "11,COMBAT_EVENT,NEW_ACTION_RESULT,FIRE,0,2345,0,1236,61771,3,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,*";
+const UNKNOWN_TRAIT: &str = "8,PLAYER_INFO,1,[142210],[1],[[HEAD,94779,T,16,ARMOR_NEW_TRAIT,LEGENDARY,640,INVALID,F,0,NORMAL],[CHEST,94780,T,16,ARMOR_NEW_TRAIT,LEGENDARY,640,INVALID,F,0,NORMAL]],[46331],[23234]";
+
+fn log() -> String {
+    format!("9,BEGIN_COMBAT\n{UNKNOWN_EVENT}\n{UNKNOWN_VALUE}\n{UNKNOWN_TRAIT}\n17,END_COMBAT\n")
+}
+
+#[test]
+fn strict_rejects_unknown_event() {
+    let e = Event::parse(UNKNOWN_EVENT).unwrap_err();
+    assert!(e.is_unknown_event());
+
+    let event = Event::parse_lenient(UNKNOWN_EVENT).unwrap();
+    match event.event() {
+        EventType::Unknown { name, raw_fields } => {
+            assert_eq!(name, "NEW_EVENT");
+            assert_eq!(raw_fields, &["1", "[2,3]", r#""Some, name""#, "T"]);
+        },
+        event => panic!("expected unknown event, got {event:?}"),
+    }
+}
+
+#[test]
+fn strict_rejects_unknown_enum_value() {
+    let e = Event::parse(UNKNOWN_VALUE).unwrap_err();
+    assert!(!e.is_unknown_event());
+    assert_eq!(e.event(), Some("COMBAT_EVENT"));
+    assert_eq!(e.token(), Some("NEW_ACTION_RESULT"));
+
+    let e = Event::parse(UNKNOWN_TRAIT).unwrap_err();
+    assert_eq!(e.event(), Some("PLAYER_INFO"));
+    assert_eq!(e.token(), Some("ARMOR_NEW_TRAIT"));
+
+    let event = Event::parse_lenient(UNKNOWN_VALUE).unwrap();
+    match event.event() {
+        EventType::CombatEvent(e) => assert_eq!(e.action_result(), &ActionResult::Other("NEW_ACTION_RESULT".into())),
+        event => panic!("expected combat event, got {event:?}"),
+    }
+    assert_eq!(event.event().unknown_values(), [("ActionResult", "NEW_ACTION_RESULT")]);
+}
+
+#[test]
+fn every_parser_agrees_on_strictness() {
+    let log = log();
+
+    let strict: Vec<_> = Event::parse_many(&log)
+        .map(|e| e.is_ok())
+        .collect();
+    assert_eq!(strict, [true, false, false, false, true]);
+
+    let par: Vec<_> = Event::parse_many_par(&log)
+        .map(|e| e.is_ok())
+        .collect();
+    assert_eq!(par, strict);
+
+    let reader: Vec<_> = Event::parse_reader(log.as_bytes())
+        .map(|e| e.is_ok())
+        .collect();
+    assert_eq!(reader, strict);
+
+    assert!(Event::parse_many_lenient(&log).all(|e| e.is_ok()));
+    assert!(Event::parse_many_par_lenient(&log).all(|e| e.is_ok()));
+    assert!(Event::parse_reader(log.as_bytes()).lenient().all(|e| e.is_ok()));
+}
+
+#[test]
+fn state_counts_unknown_events_and_values() {
+    let log = log();
+
+    let mut state = State::new();
+    state.handle_events(Event::parse_many_lenient(&log).map(Result::unwrap));
+    state.reset();
+    state.handle_events(Event::parse_many_lenient(&log).map(Result::unwrap));
+
+    // counters are kept by `reset`
+    assert_eq!(state.unknown_events()["NEW_EVENT"], 2);
+    assert_eq!(state.unknown_values()["ActionResult"]["NEW_ACTION_RESULT"], 2);
+    assert_eq!(state.unknown_values()["Trait"]["ARMOR_NEW_TRAIT"], 4);
+    assert!(!state.unknown_values().contains_key("EnchantType"));
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use common::{log_of_len, TempDir};
+use eso_lib::{Event, LogFile};
+use rayon::prelude::ParallelIterator;
+
+const PAGE_SIZE: usize = 4096;
+
+fn open(log: &std::path::Path) -> LogFile {
+    // SAFETY: file is not modified by the test
+    unsafe { LogFile::open(log).unwrap() }
+}
+
+/// parses file with every method, and compares results with parsing from `String`
+fn assert_parses_like_string(name: &str, data: &str) {
+    let dir = TempDir::new(name);
+    let file = open(&dir.write("Encounter.log", data));
+
+    let expected: Vec<_> = Event::parse_many(&data)
+        .map(|e| e.unwrap().dump().unwrap())
+        .collect();
+
+    let events: Vec<_> = file.events()
+        .map(|e| e.unwrap().dump().unwrap())
+        .collect();
+    assert_eq!(events, expected);
+
+    let events: Vec<_> = file.events_par()
+        .map(|e| e.unwrap().dump().unwrap())
+        .collect();
+    assert_eq!(events, expected);
+
+    let store = file.event_store().unwrap();
+    let events: Vec<_> = store.events().iter()
+        .map(|e| e.dump().unwrap())
+        .collect();
+    assert_eq!(events, expected);
+}
+
+#[test]
+fn file_of_whole_pages() {
+    // nothing past the end of the file is mapped, so lines at the end can't be read unguarded
+    assert_parses_like_string("whole_page", &log_of_len(PAGE_SIZE, true));
+    assert_parses_like_string("whole_pages", &log_of_len(2 * PAGE_SIZE, false));
+}
+
+#[test]
+fn last_line_inside_padding_window() {
+    for (i, missing) in [1, 5, 20, 31, 32, 33].into_iter().enumerate() {
+        let data = log_of_len(2 * PAGE_SIZE - missing, i % 2 == 0);
+        assert_parses_like_string(&format!("padding_{missing}"), &data);
+    }
+}
+
+#[test]
+fn empty_file() {
+    let dir = TempDir::new("empty");
+    let file = open(&dir.write("Encounter.log", ""));
+
+    assert_eq!(file.as_str(), "");
+    assert_eq!(file.events().count(), 0);
+    assert_eq!(file.events_par().count(), 0);
+    assert!(file.event_store().unwrap().is_empty());
+    assert_eq!(file.index().fights().len(), 0);
+}
@@ -0,0 +1,95 @@
+#[macro_use]
+mod common;
+
+use std::{fs::{self, OpenOptions}, io::Write, path::Path};
+
+use common::{TempDir, BEGIN_LOG};
+use eso_lib::{events::common::UnitId, FollowEvent, LogFollower};
+
+const BEGIN_LOG_2: &str = begin_log!(1700000100000);
+const BEGIN_LOG_3: &str = begin_log!(1700000200000);
+const PLAYER: &str = r#"3,UNIT_ADDED,1,PLAYER,T,1,0,F,6,7,"Player Name","@account",123456789,50,1800,0,PLAYER_ALLY,T"#;
+const BOSS: &str = r#"4,UNIT_ADDED,2,MONSTER,F,0,12345,T,0,0,"Z'Maja","",0,50,160,0,HOSTILE,F"#;
+const UNKNOWN: &str = r#"5,NEW_EVENT,1,[2,3],"Some, name",T"#;
+
+const RESTARTED: &str = "RESTARTED";
+
+fn append(log: &Path, data: &str) {
+    OpenOptions::new()
+        .append(true)
+        .open(log)
+        .unwrap()
+        .write_all(data.as_bytes())
+        .unwrap();
+}
+
+/// move file away, and create a new one in its place
+fn rotate(log: &Path, data: &str) {
+    fs::rename(log, log.with_extension("old")).unwrap();
+    fs::write(log, data).unwrap();
+}
+
+/// every line available right now
+fn poll(follower: &mut LogFollower) -> Vec<String> {
+    follower
+        .map(|e| match e.unwrap() {
+            FollowEvent::Event(event) => event.dump().unwrap(),
+            FollowEvent::Restarted => RESTARTED.to_owned(),
+        })
+        .collect()
+}
+
+#[test]
+fn follows_appends_truncation_and_rotation() {
+    let dir = TempDir::new("steps");
+    let log = dir.write("Encounter.log", "");
+
+    // partial line is buffered, until rest of it is written
+    let (head, tail) = PLAYER.split_at(20);
+    append(&log, &format!("{BEGIN_LOG}\n{head}"));
+
+    let mut follower = LogFollower::open(&log).unwrap().lenient();
+    assert_eq!(poll(&mut follower), [BEGIN_LOG]);
+    assert_eq!(follower.state().entities().len(), 1);
+
+    append(&log, &format!("{tail}\n{UNKNOWN}\n"));
+    assert_eq!(poll(&mut follower), [PLAYER, UNKNOWN]);
+    assert_eq!(follower.state().find_unit(&UnitId(1)).unwrap().name(), "Player Name");
+    assert_eq!(follower.state().unknown_events()["NEW_EVENT"], 1);
+
+    // nothing new was written
+    assert!(poll(&mut follower).is_empty());
+
+    // truncated, to shorter content
+    fs::write(&log, format!("{BEGIN_LOG_2}\n{BOSS}\n")).unwrap();
+    assert_eq!(poll(&mut follower), [RESTARTED, BEGIN_LOG_2, BOSS]);
+    assert!(follower.state().find_unit(&UnitId(1)).is_none());
+    assert_eq!(follower.state().find_unit(&UnitId(2)).unwrap().name(), "Z'Maja");
+    // skipped events are still reported
+    assert_eq!(follower.state().unknown_events()["NEW_EVENT"], 1);
+
+    // rotated, with a new file
+    rotate(&log, &format!("{BEGIN_LOG_3}\n{PLAYER}\n"));
+    assert_eq!(poll(&mut follower), [RESTARTED, BEGIN_LOG_3, PLAYER]);
+    assert!(follower.state().find_unit(&UnitId(2)).is_none());
+    assert!(follower.state().find_unit(&UnitId(1)).is_some());
+
+    append(&log, &format!("{BOSS}\n"));
+    assert_eq!(poll(&mut follower), [BOSS]);
+}
+
+#[test]
+fn notices_truncation_past_old_position() {
+    let dir = TempDir::new("regrown");
+    let log = dir.write("Encounter.log", &format!("{BEGIN_LOG}\n{PLAYER}\n"));
+
+    let mut follower = LogFollower::open(&log).unwrap();
+    assert_eq!(poll(&mut follower), [BEGIN_LOG, PLAYER]);
+
+    // file is longer than before the next poll, so only the `BEGIN_LOG` tells it apart
+    fs::write(&log, format!("{BEGIN_LOG_2}\n{BOSS}\n{BOSS}\n{BOSS}\n")).unwrap();
+    assert_eq!(poll(&mut follower), [RESTARTED, BEGIN_LOG_2, BOSS, BOSS, BOSS]);
+
+    assert!(follower.state().find_unit(&UnitId(1)).is_none());
+    assert!(follower.state().find_unit(&UnitId(2)).is_some());
+}
@@ -0,0 +1,138 @@
+#[macro_use]
+mod common;
+
+use std::{fs::{self, File}, io::Cursor, time::{Duration, SystemTime}};
+
+use common::TempDir;
+use eso_lib::{Event, EventKind, LogIndex, LogSchema};
+
+const LOG: &str = concat!(legacy_begin_log!(), r#"
+1,ZONE_CHANGED,1051,"Cloudrest",VETERAN
+2,BEGIN_COMBAT
+3,UNIT_REMOVED,2
+4,END_COMBAT
+5,BEGIN_COMBAT
+6,UNIT_REMOVED,3
+"#, begin_log!(), "
+10,BEGIN_TRIAL,12,1700000000000
+20,BEGIN_COMBAT
+30,EFFECT_INFO,61771,BUFF,NONE,DEFAULT
+40,END_COMBAT
+");
+
+fn lines(s: &str) -> Vec<&str> {
+    s.lines().collect()
+}
+
+#[test]
+fn finds_sessions_and_fights() {
+    let index = LogIndex::build(LOG);
+
+    assert_eq!(index.sessions().len(), 2);
+    assert_eq!(*index.sessions()[0].schema(), LogSchema::Legacy);
+    assert_eq!(*index.sessions()[1].schema(), LogSchema::Current);
+    assert_eq!(
+        index.markers().iter().map(|m| *m.kind()).collect::<Vec<_>>(),
+        [
+            EventKind::ZoneChanged, EventKind::BeginCombat, EventKind::EndCombat, EventKind::BeginCombat,
+            EventKind::BeginTrial, EventKind::BeginCombat, EventKind::EndCombat,
+        ],
+    );
+
+    let fights: Vec<_> = index.fights()
+        .into_iter()
+        .map(|range| lines(&LOG[range.start as usize..range.end as usize]))
+        .collect();
+
+    assert_eq!(fights, [
+        lines("2,BEGIN_COMBAT\n3,UNIT_REMOVED,2\n4,END_COMBAT"),
+        // never ended, lasts until the next session
+        lines("5,BEGIN_COMBAT\n6,UNIT_REMOVED,3"),
+        lines("20,BEGIN_COMBAT\n30,EFFECT_INFO,61771,BUFF,NONE,DEFAULT\n40,END_COMBAT"),
+    ]);
+}
+
+#[test]
+fn time_range_covers_requested_lines() {
+    let index = LogIndex::build(LOG);
+    let range = index.time_range(1, Duration::from_millis(20), Duration::from_millis(30)).unwrap();
+    let data = &LOG[range.start as usize..range.end as usize];
+
+    let timestamps: Vec<_> = Event::parse_many_with_schema(&data, index.schema_at(range.start))
+        .map(|e| e.unwrap().timestamp().0.as_millis())
+        .filter(|t| (20..=30).contains(t))
+        .collect();
+
+    assert_eq!(timestamps, [20, 30]);
+}
+
+/// index without the metadata of the file
+fn content(index: &LogIndex) -> impl PartialEq + std::fmt::Debug + '_ {
+    (index.log_len(), index.sessions(), index.markers(), index.checkpoints())
+}
+
+#[test]
+fn sidecar_round_trip() {
+    let index = LogIndex::build(LOG);
+    assert_eq!(LogIndex::from_reader(Cursor::new(LOG)).unwrap(), index);
+
+    let dir = TempDir::new("round_trip");
+    let log = dir.write("Encounter.log", LOG);
+
+    let built = LogIndex::load_or_build(&log).unwrap();
+    let loaded = LogIndex::load(LogIndex::sidecar_path(&log)).unwrap();
+
+    assert_eq!(content(&built), content(&index));
+    assert_eq!(*built.log_modified(), fs::metadata(&log).unwrap().modified().ok());
+    assert_eq!(loaded, built);
+}
+
+#[test]
+fn sidecar_is_rebuilt_when_log_changes() {
+    let dir = TempDir::new("stale");
+    let log = dir.write("Encounter.log", LOG);
+    let set_modified = |time: SystemTime| File::options().write(true).open(&log).unwrap().set_modified(time).unwrap();
+
+    set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    let first = LogIndex::load_or_build(&log).unwrap();
+
+    // same length, different content and modification time
+    let rewritten = LOG.replacen("2,BEGIN_COMBAT", "2,END_COMBAT__", 1);
+    assert_eq!(rewritten.len(), LOG.len());
+    dir.write("Encounter.log", &rewritten);
+    set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_100));
+
+    let second = LogIndex::load_or_build(&log).unwrap();
+    assert_ne!(second.log_modified(), first.log_modified());
+    let expected = LogIndex::build(&rewritten);
+    assert_eq!(content(&second), content(&expected));
+    assert_eq!(LogIndex::load(LogIndex::sidecar_path(&log)).unwrap(), second);
+
+    // unchanged log is not indexed again
+    assert_eq!(LogIndex::load_or_build(&log).unwrap(), second);
+}
+
+#[test]
+fn sidecar_stores_event_names() {
+    let dir = TempDir::new("names");
+    let log = dir.write("Encounter.log", LOG);
+    let sidecar = LogIndex::sidecar_path(&log);
+    LogIndex::load_or_build(&log).unwrap();
+
+    let data = fs::read(&sidecar).unwrap();
+    let find = |name: &str| data.windows(name.len()).position(|w| w == name.as_bytes());
+    assert!(find("BEGIN_COMBAT").is_some());
+    assert!(find("ZONE_CHANGED").is_some());
+
+    // unknown names are rejected
+    let mut renamed = data.clone();
+    renamed[find("BEGIN_TRIAL").unwrap() + "BEGIN_TRIAL".len() - 1] = b'X';
+    fs::write(&sidecar, renamed).unwrap();
+    assert!(LogIndex::load(&sidecar).is_err());
+
+    // so is the previous version of the format
+    let mut old = data.clone();
+    old[8..12].copy_from_slice(&1u32.to_le_bytes());
+    fs::write(&sidecar, old).unwrap();
+    assert!(LogIndex::load(&sidecar).is_err());
+}
@@ -0,0 +1,111 @@
+#[macro_use]
+mod common;
+
+use eso_lib::{events::common::AbilityId, DisplayBehaviour, Event, EventKind, EventStore, LogSchema};
+use rayon::prelude::ParallelIterator;
+
+use DisplayBehaviour::*;
+
+/// sessions of legacy and current logs, `,BEGIN_LOG,` inside of the unit name must not change the schema
+const LOG: &str = concat!("5,EFFECT_INFO,1,BUFF,NONE,ALWAYS\n", legacy_begin_log!(), r#"
+6,EFFECT_INFO,2,BUFF,NONE,T
+7,EFFECT_INFO,3,DEBUFF,SNARE,F,12345
+3,UNIT_ADDED,1,PLAYER,T,1,0,F,6,7,"Some,BEGIN_LOG,Name","@account",123456789,50,1800,0,PLAYER_ALLY,T
+8,EFFECT_INFO,4,BUFF,NONE,T
+"#, begin_log!(), "
+6,EFFECT_INFO,5,BUFF,NONE,NEVER
+7,EFFECT_INFO,6,DEBUFF,SNARE,DEFAULT,12345
+", legacy_begin_log!(1600000100000), "
+6,EFFECT_INFO,7,BUFF,NONE,F");
+
+/// (ability id, effect bar display behaviour, synergy) of every `EFFECT_INFO`
+type Effect = (u64, DisplayBehaviour, Option<u64>);
+
+fn expected() -> Vec<Effect> {
+    vec![
+        (1, Always, None),
+        (2, Never, None),
+        (3, Default, Some(12345)),
+        (4, Never, None),
+        (5, Never, None),
+        (6, Default, Some(12345)),
+        (7, Default, None),
+    ]
+}
+
+fn effects<'a>(events: impl IntoIterator<Item = &'a Event<'a>>) -> Vec<Effect> {
+    events.into_iter()
+        .filter_map(|e| e.event().effect_info())
+        .map(|e| (
+            e.ability_id().0,
+            e.effect_bar_display_behaviour().clone(),
+            e.grants_synergy().map(|AbilityId(id)| id),
+        ))
+        .collect()
+}
+
+fn parse_all(log: &str) -> Vec<Vec<Effect>> {
+    let sequential: Vec<_> = Event::parse_many(&log)
+        .map(Result::unwrap)
+        .collect();
+
+    let parallel: Vec<_> = Event::parse_many_par(&log)
+        .map(Result::unwrap)
+        .collect();
+
+    let reader: Vec<_> = Event::parse_reader(log.as_bytes())
+        .map(Result::unwrap)
+        .collect();
+
+    let filtered: Vec<_> = Event::parse_many_filtered(&log, EventKind::EffectInfo.into())
+        .map(Result::unwrap)
+        .collect();
+
+    let store = EventStore::parse(&log).unwrap();
+
+    vec![
+        effects(&sequential),
+        effects(&parallel),
+        effects(&reader),
+        effects(&filtered),
+        effects(store.events()),
+    ]
+}
+
+#[test]
+fn every_session_uses_its_schema() {
+    for effects in parse_all(LOG) {
+        assert_eq!(effects, expected());
+    }
+}
+
+#[test]
+fn every_session_uses_its_schema_crlf() {
+    let log = LOG.replace('\n', "\r\n");
+
+    for effects in parse_all(&log) {
+        assert_eq!(effects, expected());
+    }
+}
+
+#[test]
+fn layout_of_other_schema_is_rejected() {
+    let legacy = "6,EFFECT_INFO,2,BUFF,NONE,T";
+    let current = "6,EFFECT_INFO,5,BUFF,NONE,NEVER";
+
+    assert!(Event::parse(legacy).is_err());
+    assert!(Event::parse_with_schema(current, LogSchema::Legacy).is_err());
+
+    let event = Event::parse_with_schema(legacy, LogSchema::Legacy).unwrap();
+    assert_eq!(effects([&event]), [(2, Never, None)]);
+
+    // lines before the first `BEGIN_LOG` use the passed schema
+    let log = format!("{legacy}\n{}", LOG.split_once('\n').unwrap().1);
+    let events: Vec<_> = Event::parse_many_with_schema(&log, LogSchema::Legacy)
+        .map(Result::unwrap)
+        .collect();
+
+    let mut expected = expected();
+    expected[0] = (2, Never, None);
+    assert_eq!(effects(&events), expected);
+}
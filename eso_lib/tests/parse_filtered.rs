@@ -0,0 +1,53 @@
+#[macro_use]
+mod common;
+
+use eso_lib::{DisplayBehaviour, Event, EventKind, EventKindSet};
+
+const LOG: &str = concat!(legacy_begin_log!(), r#"
+6,EFFECT_INFO,61771,BUFF,NONE,T
+7,UNIT_REMOVED,2
+8,NEW_EVENT,1,[2,3],"Some, name",T
+"#, begin_log!(), "
+6,EFFECT_INFO,61771,BUFF,NONE,DEFAULT
+7,UNIT_REMOVED,3
+8,END_LOG");
+
+#[test]
+fn yields_only_wanted_kinds() {
+    let kinds = EventKindSet::from(EventKind::UnitRemoved) | EventKind::EndLog;
+
+    let filtered: Vec<_> = Event::parse_many_filtered(&LOG, kinds)
+        .map(|e| e.unwrap().dump().unwrap())
+        .collect();
+
+    let expected: Vec<_> = Event::parse_many_lenient(&LOG)
+        .map(Result::unwrap)
+        .filter(|e| kinds.contains(e.kind()))
+        .map(|e| e.dump().unwrap())
+        .collect();
+
+    assert_eq!(filtered, expected);
+    assert_eq!(filtered.len(), 3);
+}
+
+#[test]
+fn schema_is_tracked_without_begin_log() {
+    let effects: Vec<_> = Event::parse_many_filtered(&LOG, EventKind::EffectInfo.into())
+        .map(|e| e.unwrap().event().effect_info().unwrap().effect_bar_display_behaviour().clone())
+        .collect();
+
+    assert_eq!(effects, [DisplayBehaviour::Never, DisplayBehaviour::Default]);
+}
+
+#[test]
+fn unknown_events_are_opt_in() {
+    let unknown = EventKindSet::from(EventKind::Unknown);
+
+    assert_eq!(Event::parse_many_filtered(&LOG, EventKindSet::empty()).count(), 0);
+    assert_eq!(
+        Event::parse_many_filtered(&LOG, unknown)
+            .map(|e| e.unwrap().event().unknown_name().unwrap().to_owned())
+            .collect::<Vec<_>>(),
+        ["NEW_EVENT"],
+    );
+}
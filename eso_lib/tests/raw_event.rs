@@ -0,0 +1,123 @@
+mod common;
+
+use std::{borrow::Cow, time::Duration};
+
+use common::BEGIN_LOG;
+use eso_lib::{eso_parser::eso_serde::RawValue, RawEvent};
+
+fn str(s: &str) -> RawValue<'_> {
+    RawValue::Str(s.into())
+}
+
+fn list<const N: usize>(values: [RawValue<'_>; N]) -> RawValue<'_> {
+    RawValue::List(values.into())
+}
+
+/// line parsed back from the dump of `event` has the same fields
+fn assert_round_trip(event: &RawEvent) {
+    let line = event.dump().unwrap();
+    let parsed = RawEvent::parse(&line).unwrap();
+
+    assert_eq!(parsed.timestamp().0, event.timestamp().0);
+    assert_eq!(parsed.name(), event.name());
+    assert_eq!(parsed.fields(), event.fields());
+}
+
+#[test]
+fn quoted_fields() {
+    let event = RawEvent::parse(r#"1,NEW_EVENT,"Z'Maja","Say ""hi"" to them","","a, b","T","12","1/2""#).unwrap();
+
+    assert_eq!(event.fields(), [
+        str("Z'Maja"),
+        str(r#"Say "hi" to them"#),
+        str(""),
+        str("a, b"),
+        // quoted tokens are always strings
+        str("T"),
+        str("12"),
+        str("1/2"),
+    ]);
+
+    // escaped quotes can't be borrowed from the line
+    assert!(matches!(event.fields()[0], RawValue::Str(Cow::Borrowed(_))));
+    assert!(matches!(event.fields()[1], RawValue::Str(Cow::Owned(_))));
+}
+
+#[test]
+fn bracketed_fields() {
+    let event = RawEvent::parse(r#"2,NEW_EVENT,[],[1,[2,[3]]],[[HEAD,94779,T],[],["a, b",*]],22738/22738"#).unwrap();
+
+    assert_eq!(event.fields(), [
+        list([]),
+        list([RawValue::Int(1), list([RawValue::Int(2), list([RawValue::Int(3)])])]),
+        list([
+            list([str("HEAD"), RawValue::Int(94779), RawValue::Bool(true)]),
+            list([]),
+            list([str("a, b"), RawValue::None]),
+        ]),
+        list([RawValue::Int(22738), RawValue::Int(22738)]),
+    ]);
+
+    assert_eq!(event.fields()[1].as_list().unwrap()[0].as_u64(), Some(1));
+    assert!(event.fields()[0].as_list().unwrap().is_empty());
+}
+
+#[test]
+fn any_event_name() {
+    let unknown = RawEvent::parse("3,NEW_EVENT").unwrap();
+    assert_eq!(unknown.timestamp().0, Duration::from_millis(3));
+    assert_eq!(unknown.name(), "NEW_EVENT");
+    assert!(unknown.fields().is_empty());
+
+    // known events are read the same way
+    let known = RawEvent::parse("11,COMBAT_EVENT,DAMAGE,PHYSICAL,0,12345,0,1234,61771,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,*").unwrap();
+    assert_eq!(known.name(), "COMBAT_EVENT");
+    assert_eq!(known.fields().len(), 18);
+    assert_eq!(known.fields()[0].as_str(), Some("DAMAGE"));
+    assert_eq!(known.fields()[14].as_f64(), Some(0.5099));
+    assert!(known.fields()[17].is_none());
+
+    assert!(RawEvent::parse("4").is_err());
+    assert!(RawEvent::parse("x,NEW_EVENT").is_err());
+}
+
+#[test]
+fn dump_reproduces_canonical_lines() {
+    // strings quoted, lists bracketed, trailing `*` kept
+    let line = r#"5,NEW_EVENT,T,F,*,-12,18446744073709551615,0.5099,"Z'Maja","Say ""hi""","",[1,[2,"a, b"]],[],*"#;
+    let event = RawEvent::parse(line).unwrap();
+
+    assert_eq!(event.dump().unwrap(), line);
+    assert_round_trip(&event);
+}
+
+#[test]
+fn dump_reads_back_into_same_fields() {
+    let lines = [
+        "11,COMBAT_EVENT,DAMAGE,PHYSICAL,0,12345,0,1234,61771,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,*",
+        r#"8,PLAYER_INFO,1,[142210],[1],[[HEAD,94779,T,16,ARMOR_DIVINES,LEGENDARY,640,INVALID,F,0,NORMAL]],[46331],[23234]"#,
+        BEGIN_LOG,
+    ];
+
+    for line in lines {
+        let event = RawEvent::parse(line).unwrap();
+        assert_round_trip(&event);
+    }
+
+    // bare identifiers are quoted, and pairs are written as lists
+    let event = RawEvent::parse("6,NEW_EVENT,HOSTILE,1/2").unwrap();
+    assert_eq!(event.dump().unwrap(), r#"6,NEW_EVENT,"HOSTILE",[1,2]"#);
+}
+
+#[test]
+fn owned_event_keeps_fields() {
+    let line = String::from(r#"7,NEW_EVENT,"Say ""hi""",[a,*],1/2"#);
+    let event = RawEvent::parse(&line).unwrap();
+    let expected = event.dump().unwrap();
+
+    let owned = event.into_owned();
+    drop(line);
+
+    assert_eq!(owned.name(), "NEW_EVENT");
+    assert_eq!(owned.dump().unwrap(), expected);
+}
@@ -0,0 +1,208 @@
+#[macro_use]
+mod common;
+
+use eso_lib::{Event, EventCacheWriter, EventKind, EventKindSet, LogWriter};
+
+const LOG: &str = concat!(begin_log!(), r#"
+1,ZONE_CHANGED,1051,"Cloudrest",VETERAN
+2,MAP_CHANGED,1502,"Cloudrest","Summerset/cloudrest_base_0"
+3,UNIT_ADDED,1,PLAYER,T,1,0,F,6,7,"Player Name","@account",123456789,50,1800,0,PLAYER_ALLY,T
+4,UNIT_ADDED,2,MONSTER,F,0,12345,T,0,0,"Z'Maja","",0,50,160,0,HOSTILE,F
+4,UNIT_ADDED,3,MONSTER,F,0,0,F,0,0,"Name, with ""quotes""","",0,50,160,1,PLAYER_ALLY,F
+5,ABILITY_INFO,61771,"Powerful Assault","/esoui/art/icons/ability_healer_019.dds",F,T
+6,EFFECT_INFO,61771,BUFF,NONE,DEFAULT
+7,EFFECT_INFO,61772,DEBUFF,NEW_STATUS_EFFECT,NEVER,12345
+8,PLAYER_INFO,1,[142210,142079],[1,1],[[HEAD,94779,T,16,ARMOR_DIVINES,LEGENDARY,640,INVALID,F,0,NORMAL],[CHEST,94780,T,16,ARMOR_DIVINES,LEGENDARY,640,MAGICKA,T,16,LEGENDARY]],[46331,24328,22259,24330,24165,86019],[23234,61919,24326,39012,86015,86011]
+9,BEGIN_COMBAT
+10,BEGIN_CAST,1000,F,1234,61771,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,2,3000000/3000000,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129
+11,COMBAT_EVENT,DAMAGE,PHYSICAL,0,12345,0,1234,61771,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,2,2987655/3000000,32167/32167,14453/14453,73/500,1000/1000,0,-0.5099,-12.0000,-3.1415
+11,COMBAT_EVENT,NEW_ACTION_RESULT,FIRE,0,2345,0,1236,61771,3,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,*
+12,EFFECT_CHANGED,GAINED,1,1235,61771,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,*
+12,EFFECT_CHANGED,FADED,1,1235,61771,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,*,1234
+13,HEALTH_REGEN,120,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129
+14,END_CAST,COMPLETED,1234
+14,END_CAST,INTERRUPTED,1234,*,2
+15,UNIT_CHANGED,1,6,7,"Player Name","@account",123456789,50,1800,0,PLAYER_ALLY,T
+17,END_COMBAT
+18,UNIT_REMOVED,2
+19,BEGIN_TRIAL,12,1700000000000
+20,TRIAL_INIT,12,T,F,123,0,F,0
+21,END_TRIAL,12,600000,T,120000,36
+22,NEW_EVENT,1,[2,3],"Some, name",T
+23,END_LOG"#);
+
+#[test]
+fn dump_reproduces_parsed_line() {
+    let mut covered = EventKindSet::empty();
+
+    for line in LOG.lines() {
+        let event = Event::parse_lenient(line)
+            .unwrap_or_else(|e| panic!("{e}: {line}"));
+        covered.insert(event.kind());
+
+        assert_eq!(event.dump().unwrap(), line);
+        assert_eq!(event.into_owned().dump().unwrap(), line);
+    }
+
+    assert_eq!(covered, EventKindSet::all(), "not every event type is covered");
+}
+
+#[test]
+fn strings_are_unescaped() {
+    let line = r#"4,UNIT_ADDED,3,MONSTER,F,0,0,F,0,0,"Name, with ""quotes""","",0,50,160,1,PLAYER_ALLY,F"#;
+    let event = Event::parse(line).unwrap();
+    let unit = event.event().unit_added().unwrap();
+
+    assert_eq!(unit.name(), r#"Name, with "quotes""#);
+    assert_eq!(unit.display_name(), "");
+}
+
+/// whether `s` points into `data`
+fn borrows_from(s: &str, data: &str) -> bool {
+    data.as_bytes().as_ptr_range().contains(&s.as_ptr())
+}
+
+#[test]
+fn strings_borrow_from_parsed_data() {
+    let line = r#"4,UNIT_ADDED,3,MONSTER,F,0,0,F,0,0,"Z'Maja","Name, with ""quotes""",0,50,160,1,PLAYER_ALLY,F"#;
+    let event = Event::parse(line).unwrap();
+    let unit = event.event().unit_added().unwrap();
+
+    assert!(borrows_from(unit.name(), line));
+    // unescaping requires a copy
+    assert!(!borrows_from(unit.display_name(), line));
+
+    for line in LOG.lines() {
+        let event = Event::parse_lenient(line).unwrap();
+        if let Some(name) = event.event().unknown_name() {
+            assert!(borrows_from(name, line));
+        }
+    }
+}
+
+#[test]
+fn into_owned_is_equal_to_original() {
+    fn assert_static(event: Event<'static>) -> Event<'static> {
+        event
+    }
+
+    for line in LOG.lines() {
+        let event = Event::parse_lenient(line).unwrap();
+        let owned = assert_static(event.clone().into_owned());
+
+        assert_eq!(format!("{owned:?}"), format!("{event:?}"));
+        assert_eq!(owned.dump().unwrap(), event.dump().unwrap());
+    }
+
+    let line = LOG.lines().find(|line| line.contains(",UNIT_ADDED,")).unwrap();
+    let event = Event::parse(line).unwrap().into_owned();
+    assert!(!borrows_from(event.event().unit_added().unwrap().name(), line));
+}
+
+#[test]
+fn log_writer_reproduces_log() {
+    let events = Event::parse_many_lenient(&LOG)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let mut writer = LogWriter::new(Vec::new());
+    writer.write_all(&events).unwrap();
+
+    let written = String::from_utf8(writer.into_inner()).unwrap();
+    assert_eq!(written, format!("{LOG}\n"));
+}
+
+#[test]
+fn event_cache_reproduces_events() {
+    let events = Event::parse_many_lenient(&LOG)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let mut writer = EventCacheWriter::new(Vec::new()).unwrap();
+    writer.write_all(&events).unwrap();
+    let cache = writer.into_inner();
+
+    let loaded = Event::parse_cache(&cache)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(loaded.len(), events.len());
+    for (loaded, event) in loaded.iter().zip(&events) {
+        assert_eq!(loaded.dump().unwrap(), event.dump().unwrap());
+    }
+
+    // truncated cache fails instead of yielding garbage
+    let truncated: Vec<_> = Event::parse_cache(&cache[..cache.len() - 1]).unwrap().collect();
+    assert!(truncated.last().unwrap().is_err());
+
+    assert!(Event::parse_cache(LOG.as_bytes()).is_err());
+
+    // so does cache written by other version of the format
+    let mut other_version = cache.clone();
+    other_version[8..12].copy_from_slice(&(eso_lib::eso_parser::eso_serde::binary::VERSION - 1).to_le_bytes());
+    assert!(Event::parse_cache(&other_version).is_err());
+}
+
+#[test]
+fn json_reproduces_events() {
+    for line in LOG.lines() {
+        let event = Event::parse_lenient(line).unwrap();
+
+        let json = serde_json::to_string(&event).unwrap();
+        let parsed: Event = serde_json::from_str(&json)
+            .unwrap_or_else(|e| panic!("{e}: {json}"));
+
+        assert_eq!(parsed.dump().unwrap(), line);
+    }
+}
+
+#[test]
+fn json_lines_reproduce_log() {
+    // quotes inside of the name are escaped in JSON, so it can't be borrowed
+    let log = format!("{LOG}\n{}", r#"22,UNIT_ADDED,4,MONSTER,F,0,0,F,0,0,"The ""Boss""","",0,50,160,0,HOSTILE,F"#);
+
+    let json_lines: String = Event::parse_many_lenient(&log)
+        .map(|e| serde_json::to_string(&e.unwrap()).unwrap() + "\n")
+        .collect();
+    assert_eq!(json_lines.lines().count(), log.lines().count());
+
+    let dumped: Vec<_> = json_lines.lines()
+        .map(|line| serde_json::from_str::<Event>(line).unwrap().dump().unwrap())
+        .collect();
+    assert_eq!(dumped, log.lines().collect::<Vec<_>>());
+}
+
+#[test]
+fn json_uses_named_fields() {
+    let line = LOG.lines().find(|line| line.contains(",PLAYER_INFO,")).unwrap();
+    let json = serde_json::to_value(Event::parse(line).unwrap()).unwrap();
+    let equipment = &json["event"]["PLAYER_INFO"]["equipment_info"][0];
+
+    assert_eq!(equipment["slot"], "HEAD");
+    assert_eq!(equipment["trait"], "ARMOR_DIVINES");
+    assert_eq!(equipment["enchant_quality"], "NORMAL");
+
+    let line = LOG.lines().find(|line| line.contains(",EFFECT_CHANGED,") && line.ends_with(",*")).unwrap();
+    let json = serde_json::to_value(Event::parse(line).unwrap()).unwrap();
+    let effect = &json["event"]["EFFECT_CHANGED"];
+
+    assert_eq!(effect["source_unit"]["health"], serde_json::json!({ "current": 22738, "max": 22738 }));
+    assert!(effect["target_unit"].is_null());
+}
+
+#[test]
+fn event_kind_names_match_log() {
+    for line in LOG.lines() {
+        let event = Event::parse_lenient(line).unwrap();
+        let name = line.split(',').nth(1).unwrap();
+
+        match event.kind() {
+            EventKind::Unknown => assert_eq!(EventKind::from_name(name), None),
+            kind => {
+                assert_eq!(kind.name(), name);
+                assert_eq!(EventKind::from_name(name), Some(kind));
+            },
+        }
+    }
+}
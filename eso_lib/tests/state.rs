@@ -0,0 +1,305 @@
+#[macro_use]
+mod common;
+
+use std::time::Duration;
+
+use common::BEGIN_LOG;
+use eso_lib::{events::common::{AbilityId, Id, MonsterId, StackCount, TrackId, UnitId}, DungeonDifficulty, EndCastReason, Event, State};
+
+const LOG: &str = concat!(begin_log!(), r#"
+1,ZONE_CHANGED,1121,"Sunspire",VETERAN
+2,MAP_CHANGED,1553,"Sunspire","elsweyr/sunspire_base"
+2,TRIAL_INIT,12,T,F,1700000000000,0,F,0
+3,UNIT_ADDED,1,PLAYER,T,1,0,F,6,7,"Player Name","@account",123456789,50,1800,0,PLAYER_ALLY,T
+4,UNIT_ADDED,2,MONSTER,F,0,12345,T,0,0,"Z'Maja","",0,50,160,0,HOSTILE,F
+5,UNIT_ADDED,3,MONSTER,F,0,0,F,0,0,"Twilight Matriarch","",0,50,160,1,PLAYER_ALLY,F
+6,UNIT_ADDED,5,MONSTER,F,0,0,F,0,0,"Clannfear","",0,50,160,0,PLAYER_ALLY,F
+7,UNIT_CHANGED,5,0,0,"Clannfear","",0,50,160,3,PLAYER_ALLY,F
+8,PLAYER_INFO,1,[142210,142079],[1,2],[[HEAD,94779,T,16,ARMOR_DIVINES,LEGENDARY,640,INVALID,F,0,NORMAL]],[46331,24328,22259],[23234,61919,24326]
+9,BEGIN_COMBAT
+10,BEGIN_CAST,2000,F,50,61771,2,3000000/3000000,0/0,0/0,0/0,0/0,0,0.5099,0.1858,4.4129,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129
+11,BEGIN_CAST,1000,T,51,24328,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,*
+12,END_CAST,COMPLETED,51
+13,END_CAST,INTERRUPTED,50,39012,1
+14,EFFECT_CHANGED,GAINED,1,60,61771,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,*
+14,EFFECT_CHANGED,GAINED,1,60,61771,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,3,10000/10000,0/0,0/0,0/0,0/0,0,0.5099,0.1858,4.4129
+15,EFFECT_CHANGED,FADED,1,60,61771,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,3,10000/10000,0/0,0/0,0/0,0/0,0,0.5099,0.1858,4.4129
+17,END_COMBAT
+18,UNIT_REMOVED,3
+19,PLAYER_INFO,1,[142210],[1],[[HEAD,94779,T,16,ARMOR_DIVINES,LEGENDARY,640,INVALID,F,0,NORMAL]],[86019,24328],[86015]
+20,UNIT_ADDED,2,MONSTER,F,0,777,F,0,0,"Shade","",0,50,160,0,HOSTILE,F
+21,END_TRIAL,12,600000,T,120000,36"#);
+
+/// id of the pet reused by a hostile monster
+const REUSED_PET_LOG: &str = concat!(begin_log!(), r#"
+1,UNIT_ADDED,1,PLAYER,T,1,0,F,6,7,"Player Name","@account",123456789,50,1800,0,PLAYER_ALLY,T
+2,UNIT_ADDED,6,MONSTER,F,0,0,F,0,0,"Twilight Matriarch","",0,50,160,1,PLAYER_ALLY,F
+3,UNIT_REMOVED,6
+4,UNIT_ADDED,6,MONSTER,F,0,777,F,0,0,"Shade","",0,50,160,0,HOSTILE,F"#);
+
+fn state() -> State {
+    state_until(Duration::MAX)
+}
+
+/// state after events up to `timestamp`
+fn state_until(timestamp: Duration) -> State {
+    let mut state = State::new();
+    state.handle_events(
+        Event::parse_many(&LOG)
+            .map(Result::unwrap)
+            .take_while(|e| e.timestamp().0 <= timestamp)
+    );
+
+    state
+}
+
+#[test]
+fn removed_units_stay_resolvable() {
+    let state = state();
+
+    assert!(!state.entities().contains_key(&UnitId(3)));
+
+    let pet = state.find_unit(&UnitId(3)).unwrap();
+    assert_eq!(pet.name(), "Twilight Matriarch");
+    assert_eq!(pet.added(), &Duration::from_millis(5));
+    assert_eq!(pet.removed(), &Some(Duration::from_millis(18)));
+
+    let player = state.find_unit(&UnitId(1)).unwrap();
+    assert_eq!(player.removed(), &None);
+
+    assert!(state.find_unit(&UnitId(4)).is_none());
+    // world, player, both pets and both units with id 2
+    assert_eq!(state.all_units().count(), 6);
+}
+
+#[test]
+fn reused_ids_resolve_by_time() {
+    let state = state();
+
+    // boss was removed at the end of combat, and its id reused
+    let boss = state.find_unit_at(&UnitId(2), Duration::from_millis(10)).unwrap();
+    assert_eq!(boss.name(), "Z'Maja");
+    assert_eq!(boss.monster_id(), &MonsterId(12345));
+    assert!(*boss.is_boss());
+    assert_eq!(boss.removed(), &Some(Duration::from_millis(17)));
+
+    let shade = state.find_unit(&UnitId(2)).unwrap();
+    assert_eq!(shade.name(), "Shade");
+    assert!(!*shade.is_boss());
+    assert_eq!(state.find_unit_at(&UnitId(2), Duration::from_millis(25)).unwrap().name(), "Shade");
+
+    assert!(state.find_unit_at(&UnitId(2), Duration::from_millis(1)).is_none());
+    // between removal of the boss and reuse of its id
+    assert!(state.find_unit_at(&UnitId(2), Duration::from_millis(19)).is_none());
+}
+
+#[test]
+fn removed_units_are_not_found_after_removal() {
+    let state = state();
+
+    let pet = state.find_unit_at(&UnitId(3), Duration::from_millis(18)).unwrap();
+    assert_eq!(pet.name(), "Twilight Matriarch");
+
+    // id wasn't reused, but the pet is already gone
+    assert!(state.find_unit_at(&UnitId(3), Duration::from_millis(25)).is_none());
+    assert!(state.find_unit(&UnitId(3)).is_some());
+}
+
+#[test]
+fn pets_resolve_to_their_owner() {
+    let state = state();
+
+    assert_eq!(state.owner_of(&UnitId(3)), Some(UnitId(1)));
+    assert_eq!(state.owner_of(&UnitId(1)), None);
+
+    // owner of the owner is followed, even though it was removed
+    assert_eq!(state.owner_of(&UnitId(5)), Some(UnitId(3)));
+    assert_eq!(state.controlling_player(&UnitId(5)), Some(UnitId(1)));
+
+    assert_eq!(state.controlling_player(&UnitId(3)), Some(UnitId(1)));
+    assert_eq!(state.controlling_player(&UnitId(1)), Some(UnitId(1)));
+    assert_eq!(state.controlling_player(&UnitId(2)), None);
+    assert_eq!(state.controlling_player(&UnitId(4)), None);
+}
+
+#[test]
+fn owners_of_reused_ids_resolve_by_time() {
+    let mut state = State::new();
+    state.handle_events(Event::parse_many(&REUSED_PET_LOG).map(Result::unwrap));
+
+    let ms = Duration::from_millis;
+
+    assert_eq!(state.owner_of_at(&UnitId(6), ms(2)), Some(UnitId(1)));
+    assert_eq!(state.owner_of_at(&UnitId(6), ms(3)), Some(UnitId(1)));
+    assert_eq!(state.owner_of_at(&UnitId(6), ms(4)), None);
+    assert_eq!(state.owner_of_at(&UnitId(6), ms(1)), None);
+
+    assert_eq!(state.controlling_player_at(&UnitId(6), ms(2)), Some(UnitId(1)));
+    assert_eq!(state.controlling_player_at(&UnitId(6), ms(4)), None);
+    assert_eq!(state.controlling_player_at(&UnitId(1), ms(4)), Some(UnitId(1)));
+
+    // newest holder of the id
+    assert_eq!(state.owner_of(&UnitId(6)), None);
+    assert_eq!(state.controlling_player(&UnitId(6)), None);
+}
+
+#[test]
+fn player_info_keeps_loadout_history() {
+    let state = state();
+    let player = state.find_unit(&UnitId(1)).unwrap();
+
+    assert_eq!(player.primary_abilities(), &[AbilityId(86019), AbilityId(24328)]);
+    assert_eq!(player.backup_abilities(), &[AbilityId(86015)]);
+    assert_eq!(player.long_term_effects().len(), 1);
+    assert_eq!(player.loadouts().len(), 2);
+
+    // loadout used during the fight
+    let loadout = player.loadout_at(Duration::from_millis(12)).unwrap();
+    assert_eq!(loadout.timestamp(), &Duration::from_millis(8));
+    assert_eq!(loadout.primary_abilities(), &[AbilityId(46331), AbilityId(24328), AbilityId(22259)]);
+    assert_eq!(loadout.backup_abilities(), &[AbilityId(23234), AbilityId(61919), AbilityId(24326)]);
+
+    let effect = &loadout.long_term_effects()[1];
+    assert_eq!(effect.ability(), &AbilityId(142079));
+    assert_eq!(effect.stack_count(), &StackCount(2));
+
+    assert!(player.loadout_at(Duration::from_millis(5)).is_none());
+}
+
+#[test]
+fn casts_are_tracked_until_their_end() {
+    let now = Duration::from_millis(11);
+    let during = state_until(now);
+
+    let boss_cast = during.casts().current_cast(&UnitId(2), now).unwrap();
+    assert_eq!(boss_cast.ability_id(), &AbilityId(61771));
+    assert_eq!(boss_cast.target_unit(), &UnitId(1));
+    assert_eq!(boss_cast.remaining(now), Duration::from_millis(1999));
+
+    let channel = during.casts().current_cast(&UnitId(1), now).unwrap();
+    assert_eq!(channel.cast_id(), &TrackId(51));
+    assert!(*channel.channeled());
+    // target is the caster itself
+    assert_eq!(channel.target_unit(), &UnitId(1));
+
+    // past the expected end
+    assert!(during.casts().current_cast(&UnitId(2), Duration::from_millis(2011)).is_none());
+    assert!(during.casts().finished().is_empty());
+
+    let state = state();
+    assert!(state.casts().active().is_empty());
+
+    let [completed, interrupted] = state.casts().finished() else { panic!() };
+    assert_eq!(completed.end().as_ref().unwrap().reason(), &Some(EndCastReason::Completed));
+
+    let end = interrupted.end().as_ref().unwrap();
+    assert_eq!(interrupted.cast_id(), &TrackId(50));
+    assert_eq!(end.reason(), &Some(EndCastReason::Interrupted));
+    assert_eq!(end.timestamp(), &Duration::from_millis(13));
+    assert_eq!(end.interrupting_ability_id(), &Some(AbilityId(39012)));
+    assert_eq!(end.interrupting_unit_id(), &Some(UnitId(1)));
+}
+
+#[test]
+fn casts_of_removed_units_are_closed() {
+    let log = format!("{}\n{}", LOG, r#"22,BEGIN_CAST,1000,F,52,61771,1,22738/22738,32167/32167,14453/14453,73/500,1000/1000,0,0.5099,0.1858,4.4129,*
+23,UNIT_REMOVED,1"#);
+
+    let mut state = State::new();
+    state.handle_events(Event::parse_many(&log).map(Result::unwrap));
+
+    assert!(state.casts().active().is_empty());
+    let removed = state.casts().finished().last().unwrap();
+    assert_eq!(removed.cast_id(), &TrackId(52));
+
+    let end = removed.end().as_ref().unwrap();
+    assert_eq!(end.reason(), &None);
+    assert_eq!(end.timestamp(), &Duration::from_millis(23));
+    assert_eq!(end.interrupting_unit_id(), &None);
+}
+
+#[test]
+fn finished_casts_are_kept_until_next_combat() {
+    assert_eq!(state().casts().finished().len(), 2);
+
+    let log = format!("{LOG}\n30,BEGIN_COMBAT");
+    let mut state = State::new();
+    state.handle_events(Event::parse_many(&log).map(Result::unwrap));
+
+    assert!(state.casts().finished().is_empty());
+}
+
+#[test]
+fn effects_sharing_track_id_are_kept_per_target() {
+    let effects = state_until(Duration::from_millis(14));
+    let effects = effects.effects();
+
+    assert_eq!(effects.get_by_id(&TrackId(60)).count(), 2);
+    assert_eq!(effects.get_granted_effects(&UnitId(1)).unwrap(), &[(TrackId(60), UnitId(1)), (TrackId(60), UnitId(3))]);
+    assert_eq!(effects.get_received_effects(&UnitId(3)).unwrap(), &[(TrackId(60), UnitId(3))]);
+
+    // fading on one target, doesn't affect the other one
+    let state = state();
+    let effects = state.effects();
+
+    assert!(effects.get(&(TrackId(60), UnitId(3))).is_none());
+    assert!(effects.get(&(TrackId(60), UnitId(1))).is_some());
+    assert_eq!(effects.get_granted_effects(&UnitId(1)).unwrap(), &[(TrackId(60), UnitId(1))]);
+    assert_eq!(effects.get_received_effects(&UnitId(1)).unwrap(), &[(TrackId(60), UnitId(1))]);
+    assert_eq!(effects.get_by_id(&TrackId(60)).count(), 1);
+    assert_eq!(effects.get_by_id(&TrackId(61)).count(), 0);
+}
+
+#[test]
+fn effects_of_removed_units_are_dropped() {
+    let mut state = state_until(Duration::from_millis(14));
+    state.handle_event(&Event::parse("16,UNIT_REMOVED,3").unwrap());
+    let effects = state.effects();
+
+    assert!(effects.get(&(TrackId(60), UnitId(3))).is_none());
+    assert!(effects.get_received_effects(&UnitId(3)).is_none());
+    assert_eq!(effects.get_granted_effects(&UnitId(1)).unwrap(), &[(TrackId(60), UnitId(1))]);
+
+    let targets: Vec<_> = effects.get_by_id(&TrackId(60))
+        .map(|e| *e.target_unit().unit_id())
+        .collect();
+    assert_eq!(targets, [UnitId(1)]);
+}
+
+#[test]
+fn zone_map_and_trial_are_tracked() {
+    let start = state_until(Duration::from_millis(2));
+
+    let zone = start.zone().as_ref().unwrap();
+    assert_eq!(zone.name(), "Sunspire");
+    assert_eq!(zone.dungeon_difficulty(), &DungeonDifficulty::Veteran);
+
+    let map = start.map().as_ref().unwrap();
+    assert_eq!(map.id(), &Id(1553));
+    assert_eq!(map.texture_path(), "elsweyr/sunspire_base");
+
+    let trial = start.trial().as_ref().unwrap();
+    assert_eq!(trial.id(), &Id(12));
+    assert!(*trial.in_progress());
+    assert!(!*trial.completed());
+    assert_eq!(trial.vitality_bonus(), &None);
+
+    let state = state();
+    let trial = state.trial().as_ref().unwrap();
+    assert!(!*trial.in_progress());
+    assert!(*trial.completed());
+    assert!(*trial.success());
+    assert_eq!(trial.start_time(), &Duration::from_millis(1700000000000));
+    assert_eq!(trial.duration(), &Duration::from_secs(600));
+    assert_eq!(trial.score(), &120000);
+    assert_eq!(trial.vitality_bonus(), &Some(36));
+}
+
+#[test]
+fn archive_is_cleared_with_new_session() {
+    let mut state = state();
+    state.handle_event(&Event::parse(BEGIN_LOG).unwrap());
+
+    assert!(state.find_unit(&UnitId(3)).is_none());
+    assert_eq!(state.all_units().count(), 1);
+}
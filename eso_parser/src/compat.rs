@@ -0,0 +1,34 @@
+//! Wrappers over nightly only APIs, with stable fallbacks used without `nightly` feature
+
+use std::ops::Range;
+
+#[cfg(feature = "nightly")]
+pub(crate) use std::intrinsics::{likely, unlikely};
+
+#[cfg(not(feature = "nightly"))]
+#[inline(always)]
+pub(crate) fn likely(b: bool) -> bool {
+    b
+}
+
+#[cfg(not(feature = "nightly"))]
+#[inline(always)]
+pub(crate) fn unlikely(b: bool) -> bool {
+    b
+}
+
+/// # SAFETY
+/// same as `std::slice::from_ptr_range`
+#[cfg(feature = "nightly")]
+#[inline(always)]
+pub(crate) unsafe fn slice_from_ptr_range<'a>(range: Range<*const u8>) -> &'a [u8] {
+    std::slice::from_ptr_range(range)
+}
+
+/// # SAFETY
+/// same as `std::slice::from_ptr_range`
+#[cfg(not(feature = "nightly"))]
+#[inline(always)]
+pub(crate) unsafe fn slice_from_ptr_range<'a>(range: Range<*const u8>) -> &'a [u8] {
+    std::slice::from_raw_parts(range.start, range.end.offset_from(range.start) as usize)
+}
@@ -1,5 +1,5 @@
-use std::simd::cmp::SimdPartialEq;
 use super::{EsoLogReaderTrait, EsoLogLineReaderTrait, EsoReaderTrait};
+use crate::{compat, simd_find};
 
 pub struct EsoLogReader;
 
@@ -42,10 +42,10 @@ impl<'a> Iterator for EsoLogReaderSplitChar<'a> {
 
     fn next(&mut self) -> Option<&'a str> {
         // SAFETY: head (0) is always <= len()
-        let end = unsafe { FastFind::find(0, self.0, self.1) };
+        let end = unsafe { simd_find::find(0, self.0, self.1) };
 
         // cast pointers to slice
-        // SAFETY: FastFind::find will always return value from range [0..=self.0.len()]
+        // SAFETY: simd_find::find will always return value from range [0..=self.0.len()]
         let result = unsafe {
             std::slice::from_raw_parts(self.0.as_ptr(), end)
         };
@@ -57,12 +57,12 @@ impl<'a> Iterator for EsoLogReaderSplitChar<'a> {
             let mut range = self.0.as_ptr_range();
             range.start = range.start.add((end + 1).min(self.0.len()));
 
-            std::slice::from_ptr_range(range)
+            compat::slice_from_ptr_range(range)
         };
 
         // if result is empty that means that self.0 is also empty, return None in that case
         // as iterator is exhausted
-        if std::intrinsics::likely(!result.is_empty()) {
+        if compat::likely(!result.is_empty()) {
             // SAFETY: `result` is a valid UTF-8 string as above reasons
             unsafe {
                 let slice = std::str::from_utf8_unchecked(result);
@@ -102,7 +102,7 @@ impl<'a> EsoLogLineReaderTrait<'a> for EsoLogReaderLine<'a> {
         let mut range = self.0.as_ptr_range();
         range.start = range.start.add(i);
 
-        self.0 = std::slice::from_ptr_range(range);
+        self.0 = compat::slice_from_ptr_range(range);
     }
 }
 
@@ -112,10 +112,19 @@ impl<'a> Iterator for EsoLogReaderLine<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         let s = self.0;
 
-        let end = match s.get(0)? {
+        let end = match s.first()? {
             b'"' => unsafe {
                 // SAFETY: head (1) is always <= s.len()
-                SimdFind::find(1, s, b'"') + 1
+                let mut end = simd_find::find(1, s, b'"') + 1;
+
+                // `""` is an escaped quote, string continues past it
+                while compat::unlikely(s.get(end) == Some(&b'"')) {
+                    // SAFETY: `end` < s.len(), so `end + 1` <= s.len()
+                    end = simd_find::find(end + 1, s, b'"') + 1;
+                }
+
+                // unterminated string ends with the buffer
+                end.min(s.len())
             },
             b'[' => {
                 let mut i = 0;
@@ -123,7 +132,7 @@ impl<'a> Iterator for EsoLogReaderLine<'a> {
 
                 // parse nested arrays
                 let end = loop {
-                    if std::intrinsics::unlikely(i == s.len()) { break i; }
+                    if compat::unlikely(i == s.len()) { break i; }
                     // SAFETY: we just checked if `i` is < s.len()
                     let c = unsafe { *self.0.get_unchecked(i) };
 
@@ -139,17 +148,17 @@ impl<'a> Iterator for EsoLogReaderLine<'a> {
             },
             _ => unsafe {
                 // SAFETY: head (0) is always <= s.len()
-                SimdFind::find(0, s, b',')
+                simd_find::find(0, s, b',')
             }
         };
 
         // &s[..end];
-        // SAFETY: SimdFind::find will return value <= s.len()
+        // SAFETY: simd_find::find will return value <= s.len()
         let r = unsafe {
             let mut range = s.as_ptr_range();
             range.end = range.start.add(end);
 
-            std::slice::from_ptr_range(range)
+            compat::slice_from_ptr_range(range)
         };
         
         // `end` points at `,`, self.0 slice pointing one byte past it
@@ -159,10 +168,10 @@ impl<'a> Iterator for EsoLogReaderLine<'a> {
             let mut range = s.as_ptr_range();
             range.start = range.start.add((end + 1).min(s.len()));
 
-            self.0 = std::slice::from_ptr_range(range);
+            self.0 = compat::slice_from_ptr_range(range);
         }
         
-        if std::intrinsics::likely(!r.is_empty()) {
+        if compat::likely(!r.is_empty()) {
             // SAFETY: buffer is guranteed to be a valid UTF-8
             let slice = unsafe { std::str::from_utf8_unchecked(r) };
 
@@ -172,75 +181,3 @@ impl<'a> Iterator for EsoLogReaderLine<'a> {
         }
     }
 }
-
-struct SimdFind;
-
-impl SimdFind {
-    // SAFETY: head <= s.len()
-    unsafe fn find(mut head: usize, s: &[u8], f: u8) -> usize {
-        type Simd = std::simd::Simd<u8, 8>;
-    
-        let mask = Simd::from_array([f; Simd::LEN]);
-        while s.len() - head >= Simd::LEN {
-            let mut range = s.as_ptr_range();
-            // SAFETY: there is atleast `Simd::LEN` bytes past head that are valid to read
-            unsafe {
-                range.start = range.start.add(head);
-                range.end = range.start.add(Simd::LEN);
-            }
-    
-            // SAFETY: range length is equal to Simd::LEN
-            let values = unsafe {
-                Simd::from_array(std::slice::from_ptr_range(range).try_into().unwrap_unchecked())
-            };
-
-            // this type must be casted to type with same amount of bits as Simd::LEN
-            // if its not we will skip some data (if its too big), or repeat our selves (if too small)
-            let result = values.simd_eq(mask).to_bitmask() as u8;
-    
-            head += result.trailing_zeros() as usize;
-    
-            if result != 0 {
-                return head;
-            }
-        }
-
-        unsafe {
-            // SAFETY: we have just checked for required conditions
-            FastFind::find_rev_start(head, s, f)
-        }
-    }
-}
-
-struct FastFind; 
-
-impl FastFind {
-    /// # SAFETY
-    /// Caller must ensure that `head <= s.len()`
-    #[inline]
-    unsafe fn find_rev_start(mut head: usize, s: &[u8], f: u8) -> usize {
-        let old_head = head;
-        head = s.len();
-    
-        for i in (old_head..s.len()).rev() {
-            if *s.get_unchecked(i) == f {
-                head = i;
-            }
-        }
-
-        head
-    }
-
-    /// # SAFETY
-    /// Caller must ensure that `head <= s.len()`
-    #[inline]
-    pub unsafe fn find(head: usize, s: &[u8], f: u8) -> usize {
-        for i in head..s.len() {
-            if *s.get_unchecked(i) == f {
-                return i;
-            }
-        }
-
-        s.len()
-    }
-}
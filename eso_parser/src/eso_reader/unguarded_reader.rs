@@ -1,5 +1,8 @@
-use std::simd::cmp::SimdPartialEq;
 use super::{EsoLogReaderTrait, EsoLogLineReaderTrait, EsoReaderTrait};
+use crate::{compat, simd_find};
+
+/// Amount of bytes past the end of the string that `UnguardedEsoLogReader` is allowed to read
+pub const UNGUARDED_PADDING: usize = 64;
 
 // unguarded version of EsoLogReader, where reading past provided str slice is allowed
 pub struct UnguardedEsoLogReader;
@@ -54,10 +57,10 @@ impl<'a> Iterator for UnguardedEsoLogReaderSplitChar<'a> {
         // end will be equal to `self.0.len()` if there is no such character
         // SAFETY: `UnguardedEsoLogReader` requires that reading past provided string
         // will not cause UB
-        let end = unsafe { SimdFind::find(0, self.0, self.1) };
+        let end = unsafe { simd_find::find_unguarded(0, self.0, self.1) };
 
         // cast pointers to slice
-        // SAFETY: SimdFind::find will always return value from range [0..=self.0.len()]
+        // SAFETY: simd_find::find_unguarded will always return value from range [0..=self.0.len()]
         let result = unsafe {
             std::slice::from_raw_parts(self.0.as_ptr(), end)
         };
@@ -69,7 +72,7 @@ impl<'a> Iterator for UnguardedEsoLogReaderSplitChar<'a> {
             let mut range = self.0.as_ptr_range();
             range.start = range.start.add((end + 1).min(self.0.len()));
 
-            std::slice::from_ptr_range(range)
+            compat::slice_from_ptr_range(range)
         };
 
         // if result is empty that means that self.0 is also empty, return None in that case
@@ -114,7 +117,7 @@ impl<'a> EsoLogLineReaderTrait<'a> for UnguardedEsoLogReaderLine<'a> {
         let mut range = self.0.as_ptr_range();
         range.start = range.start.add(i);
 
-        self.0 = std::slice::from_ptr_range(range);
+        self.0 = compat::slice_from_ptr_range(range);
     }
 }
 
@@ -124,11 +127,20 @@ impl<'a> Iterator for UnguardedEsoLogReaderLine<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         let s = self.0;
 
-        let end = match s.get(0)? {
+        let end = match s.first()? {
             b'"' => unsafe {
                 // SAFETY: caller guaranteed that reading past this string's buffer is safe
                 // when they created this object instance
-                SimdFind::find(1, s, b'"') + 1
+                let mut end = simd_find::find_unguarded(1, s, b'"') + 1;
+
+                // `""` is an escaped quote, string continues past it
+                while compat::unlikely(s.get(end) == Some(&b'"')) {
+                    // SAFETY: `end` < s.len(), so `end + 1` <= s.len()
+                    end = simd_find::find_unguarded(end + 1, s, b'"') + 1;
+                }
+
+                // unterminated string ends with the buffer
+                end.min(s.len())
             },
             b'[' => {
                 let mut i = 0;
@@ -136,7 +148,7 @@ impl<'a> Iterator for UnguardedEsoLogReaderLine<'a> {
 
                 // parse nested arrays
                 let end = loop {
-                    if std::intrinsics::unlikely(i == s.len()) { break i; }
+                    if compat::unlikely(i == s.len()) { break i; }
                     // SAFETY: we just checked if `i` is < s.len()
                     let c = unsafe { *self.0.get_unchecked(i) };
 
@@ -153,17 +165,17 @@ impl<'a> Iterator for UnguardedEsoLogReaderLine<'a> {
             _ => unsafe {
                 // SAFETY: caller guaranteed that reading past this string's buffer is safe
                 // when they created this object instance
-                SimdFind::find(0, s, b',')
+                simd_find::find_unguarded(0, s, b',')
             }
         };
 
         // &s[..end] without bound checking
-        // SAFETY: SimdFind::find will return value <= s.len()
+        // SAFETY: simd_find::find_unguarded will return value <= s.len()
         let r = unsafe {
             let mut range = s.as_ptr_range();
             range.end = range.start.add(end);
 
-            std::slice::from_ptr_range(range)
+            compat::slice_from_ptr_range(range)
         };
         
         // `end` points at `,`, self.0 slice pointing one byte past it
@@ -173,10 +185,10 @@ impl<'a> Iterator for UnguardedEsoLogReaderLine<'a> {
             let mut range = s.as_ptr_range();
             range.start = range.start.add((end + 1).min(s.len()));
 
-            self.0 = std::slice::from_ptr_range(range);
+            self.0 = compat::slice_from_ptr_range(range);
         }
         
-        if std::intrinsics::likely(!r.is_empty()) {
+        if compat::likely(!r.is_empty()) {
             // SAFETY: buffer is guranteed to be a valid UTF-8
             let slice = unsafe { std::str::from_utf8_unchecked(r) };
 
@@ -186,32 +198,3 @@ impl<'a> Iterator for UnguardedEsoLogReaderLine<'a> {
         }
     }
 }
-
-struct SimdFind;
-
-impl SimdFind {
-    /// # SAFETY
-    /// Caller must ensure that reading up to `31` past the passed string is safe
-    unsafe fn find(mut head: usize, s: &[u8], f: u8) -> usize {
-        type Simd = std::simd::Simd<u8, 32>;
-
-        let mask = Simd::from_array([f; Simd::LEN]);
-        while head < s.len() {
-            let mut range = s.as_ptr_range();
-            range.start = range.start.add(head);
-            range.end = range.start.add(Simd::LEN);
-    
-            let values = Simd::from_array(std::slice::from_ptr_range(range).try_into().unwrap_unchecked());
-            // SAFETY: this type must be casted to type with same amount of bits as Simd::LEN
-            let result = values.simd_eq(mask).to_bitmask() as u32;
-    
-            head += result.trailing_zeros() as usize;
-    
-            if result != 0 {
-                return head.min(s.len());
-            }
-        }
-    
-        s.len()
-    }
-}
@@ -0,0 +1,413 @@
+use serde::de::{self, DeserializeSeed, IntoDeserializer, SeqAccess, EnumAccess, VariantAccess};
+
+use super::{MAGIC, VERSION};
+use crate::eso_serde::{Error, ErrorKind};
+
+/// Deserializes values from the binary format, strings are borrowed from the input
+pub struct Deserializer<'de> {
+    input: &'de [u8],
+    // every string read so far, by its index
+    strings: Vec<&'de str>,
+}
+
+impl<'de> Deserializer<'de> {
+    /// create deserializer, validating header of the stream
+    pub fn new(input: &'de [u8]) -> Result<Self, Error> {
+        let header_len = MAGIC.len() + 4;
+        if input.len() < header_len || &input[..MAGIC.len()] != MAGIC {
+            return Err(ErrorKind::InvalidHeader.into());
+        }
+
+        let version = u32::from_le_bytes(input[MAGIC.len()..header_len].try_into().unwrap());
+        if version != VERSION {
+            return Err(ErrorKind::InvalidHeader.into());
+        }
+
+        Ok(Self {
+            input: &input[header_len..],
+            strings: Vec::new(),
+        })
+    }
+
+    pub fn is_depleted(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    #[inline]
+    fn take(&mut self, len: usize) -> Result<&'de [u8], Error> {
+        if self.input.len() < len {
+            return Err(unexpected_end());
+        }
+
+        let (bytes, rest) = self.input.split_at(len);
+        self.input = rest;
+
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn read_byte(&mut self) -> Result<u8, Error> {
+        let Some((&byte, rest)) = self.input.split_first() else {
+            return Err(unexpected_end());
+        };
+        self.input = rest;
+
+        Ok(byte)
+    }
+
+    #[inline]
+    fn read_varint(&mut self) -> Result<u64, Error> {
+        let mut v = 0u64;
+
+        // u64 takes at most 10 bytes
+        for (i, &byte) in self.input.iter().take(10).enumerate() {
+            v |= ((byte & 0x7f) as u64) << (7 * i);
+
+            if byte & 0x80 == 0 {
+                self.input = &self.input[i + 1..];
+                return Ok(v);
+            }
+        }
+
+        if self.input.len() < 10 {
+            Err(unexpected_end())
+        } else {
+            Err(invalid_token())
+        }
+    }
+
+    #[inline]
+    fn read_signed(&mut self) -> Result<i64, Error> {
+        let v = self.read_varint()?;
+
+        Ok((v >> 1) as i64 ^ -((v & 1) as i64))
+    }
+
+    #[inline]
+    fn read_bool(&mut self) -> Result<bool, Error> {
+        match self.read_byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid_token()),
+        }
+    }
+
+    #[inline]
+    fn read_str(&mut self) -> Result<&'de str, Error> {
+        match self.read_varint()? {
+            0 => {
+                let len = self.read_varint()? as usize;
+                let s = std::str::from_utf8(self.take(len)?)?;
+                self.strings.push(s);
+
+                Ok(s)
+            },
+            index => self.strings
+                .get(index as usize - 1)
+                .copied()
+                .ok_or_else(invalid_token),
+        }
+    }
+
+    #[inline]
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+}
+
+// errors are rare, keep them out of the hot paths
+#[cold]
+fn unexpected_end() -> Error {
+    ErrorKind::UnexpectedEnd.into()
+}
+
+#[cold]
+fn invalid_token() -> Error {
+    ErrorKind::InvalidToken.into()
+}
+
+macro_rules! deserialize_number {
+    ($fn:ident, $visit:ident, $read:ident) => {
+        #[inline]
+        fn $fn<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            let v = self.$read()?;
+
+            visitor.$visit(v.try_into().map_err(|_| invalid_token())?)
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(ErrorKind::UnsupportedOperation("binary format is not self-describing").into())
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_bool(self.read_bool()?)
+    }
+
+    deserialize_number!(deserialize_i8, visit_i8, read_signed);
+    deserialize_number!(deserialize_i16, visit_i16, read_signed);
+    deserialize_number!(deserialize_i32, visit_i32, read_signed);
+    deserialize_number!(deserialize_i64, visit_i64, read_signed);
+    deserialize_number!(deserialize_u8, visit_u8, read_varint);
+    deserialize_number!(deserialize_u16, visit_u16, read_varint);
+    deserialize_number!(deserialize_u32, visit_u32, read_varint);
+    deserialize_number!(deserialize_u64, visit_u64, read_varint);
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_f32(f32::from_le_bytes(self.read_array()?))
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_f64(f64::from_le_bytes(self.read_array()?))
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let c = u32::try_from(self.read_varint()?).ok()
+            .and_then(char::from_u32)
+            .ok_or_else(invalid_token)?;
+
+        visitor.visit_char(c)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_borrowed_str(self.read_str()?)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(ErrorKind::UnsupportedOperation("Bytes deserialization is not supported by this deserializer").into())
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        if self.read_bool()? {
+            visitor.visit_some(self)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let len = self.read_varint()? as usize;
+
+        visitor.visit_seq(Fields { de: self, remaining: len })
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(Fields { de: self, remaining: len })
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(ErrorKind::UnsupportedOperation("Map deserialization is not supported by this deserializer").into())
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+}
+
+/// Fixed amount of consecutive values
+struct Fields<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de> SeqAccess<'de> for Fields<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> EnumAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = self.read_str()?;
+        let value = seed.deserialize(variant.into_deserializer())
+            .map_err(|e: Error| e.with_token(variant))?;
+
+        Ok((value, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
@@ -0,0 +1,24 @@
+//! Compact binary format, for caching parsed events
+//!
+//! - stream starts with `MAGIC` followed by `VERSION` (little endian `u32`)
+//! - integers are LEB128 varints, signed ones zigzag encoded
+//! - floats are little endian IEEE 754, bools and `Option` tags a single byte
+//! - strings (including enum variant names) are interned, first occurrence is written
+//!   as varint `0`, its length and bytes, every next one as varint `index + 1` in the string table
+//! - sequences are prefixed with their length, structs and tuples are written field after field
+//!
+//! Format is not self-describing, data must be deserialized into the same types it was serialized from
+
+mod serializer;
+mod deserializer;
+
+pub use serializer::*;
+pub use deserializer::*;
+
+/// Identifies binary stream, followed by `VERSION`
+pub const MAGIC: &[u8; 8] = b"ESOBIN\0\0";
+
+/// Version of the format, bumped on every incompatible change
+///
+/// - 2: events unknown to the library are written as a regular variant, instead of after a separate tag
+pub const VERSION: u32 = 2;
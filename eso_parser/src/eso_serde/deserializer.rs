@@ -1,10 +1,10 @@
 
 use std::marker::PhantomData;
 
-use serde::de::{self, SeqAccess, EnumAccess, VariantAccess};
+use serde::{de::{self, SeqAccess, EnumAccess, VariantAccess}, forward_to_deserialize_any};
 
-use crate::{EsoLogLineReaderTrait, EsoLogReaderTrait, EsoReaderTrait, number_parser::NumberParser};
-use super::error::Error;
+use crate::{EsoLogReader, EsoLogLineReaderTrait, EsoLogReaderTrait, EsoReaderTrait, number_parser::NumberParser};
+use super::{error::{Error, ErrorKind}, newtypes::{BRACKETED, SLASH_SEPARATED}};
 
 /// The Elder Scrolls Online ocmpatible encounter log format deserializer
 ///
@@ -12,6 +12,8 @@ use super::error::Error;
 /// eg. `EsoLogReader` or `UnguardedEsoLogReader`
 pub struct Deserializer<'de, Reader: EsoReaderTrait<'de>> {
     s: Reader::LineReader,
+    // last token returned from `next`, used for error reporting
+    last_token: &'de str,
     _phantom: PhantomData<&'de ()>,
 }
 
@@ -19,27 +21,49 @@ impl<'de, Reader: EsoReaderTrait<'de>> Deserializer<'de, Reader> {
     pub fn new(s: &'de str) -> Self {
         Self {
             s: Reader::read_line(s),
+            last_token: "",
             _phantom: Default::default(),
         }
     }
 
+    /// deserialize every remaining field of the line as a sequence,
+    /// type of every field is inferred from its token (see `RawValue`)
+    pub fn deserialize_line<V>(&mut self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(LineWrapper(self))
+    }
+
     #[inline]
     pub fn is_depleted(&self) -> bool {
         self.s.is_depleted()
     }
 
+    /// part of the line that was not deserialized yet
+    #[inline]
+    pub fn remaining(&self) -> &'de str {
+        self.s.inner()
+    }
+
     #[inline]
     fn next(&mut self) -> Result<&'de str, Error> {
         if let Some(value) = Iterator::next(&mut self.s) {
+            self.last_token = value;
             Ok(value)
         } else {
-            Err(Error::UnexpectedEnd)
+            Err(ErrorKind::UnexpectedEnd.into())
         }
     }
 
     #[inline]
     fn read_number<T: NumberParser>(&mut self) -> Result<T, Error> {
-        let (number, parsed_bytes) = T::parse(self.s.inner().as_bytes())?;
+        let inner = self.s.inner();
+        let (number, parsed_bytes) = T::parse(inner.as_bytes())
+            .map_err(|e| {
+                let token = inner.split(',').next().unwrap_or_default();
+                e.with_token(token)
+            })?;
         unsafe { self.s.advance(parsed_bytes) };
 
         Ok(number)
@@ -56,38 +80,72 @@ impl<'de, Reader: EsoReaderTrait<'de>> VecWrapper<'de, Reader> {
         && slice.last() == Some(&b']') {
             Ok(Self(Deserializer {
                 s: Reader::read_vec(s),
+                last_token: "",
                 _phantom: Default::default(),
             }))
         } else {
-            Err(Error::NotAList)
+            Err(Error::from(ErrorKind::NotAList).with_token(s))
         }
     }
 }
 
-struct SeqWrapper<'de, 'a, Reader: EsoReaderTrait<'de>>(&'a mut Deserializer<'de, Reader>);
+/// Every remaining field of the line, see `Deserializer::deserialize_line`
+struct LineWrapper<'de, 'a, Reader: EsoReaderTrait<'de>>(&'a mut Deserializer<'de, Reader>);
+
+/// Every value of `a/b` token
+struct PairWrapper<'de, Reader: EsoReaderTrait<'de>>(Reader::SplitReader);
+
+/// Deserializer of a single token, that infers its type
+struct TokenDeserializer<'de, Reader: EsoReaderTrait<'de>>(&'de str, PhantomData<Reader>);
+
+struct SeqWrapper<'de, 'a, Reader: EsoReaderTrait<'de>> {
+    de: &'a mut Deserializer<'de, Reader>,
+    // names of the struct fields, empty for tuples
+    fields: &'static [&'static str],
+    index: usize,
+}
 
 impl<'de, 'a, Reader: EsoReaderTrait<'de>> SeqWrapper<'de, 'a, Reader> {
     fn new(de: &'a mut Deserializer<'de, Reader>, _len: usize) -> Self {
-        Self(de)
+        Self::with_fields(de, &[])
+    }
+
+    fn with_fields(de: &'a mut Deserializer<'de, Reader>, fields: &'static [&'static str]) -> Self {
+        Self {
+            de,
+            fields,
+            index: 0,
+        }
     }
 }
 
-struct Enum<'de, 'a, Reader: EsoReaderTrait<'de>>(&'a mut Deserializer<'de, Reader>);
+struct Enum<'de, 'a, Reader: EsoReaderTrait<'de>> {
+    de: &'a mut Deserializer<'de, Reader>,
+    // token the variant was read from
+    variant: &'de str,
+}
 
 impl<'de, 'a, Reader: EsoReaderTrait<'de>> Enum<'de, 'a, Reader> {
     fn new(de: &'a mut Deserializer<'de, Reader>) -> Self {
-        Self(de)
+        Self {
+            de,
+            variant: "",
+        }
     }
 }
 
-impl<'de, 'a, Reader: EsoReaderTrait<'de>> de::Deserializer<'de> for &'a mut Deserializer<'de, Reader> {
+impl<'de, Reader: EsoReaderTrait<'de>> de::Deserializer<'de> for &mut Deserializer<'de, Reader> {
     type Error = Error;
 
-    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    /// type of the next field is inferred from its token
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        todo!()
+        let token = self.next()?;
+
+        visit_token::<V, Reader>(token, visitor)
+            .map_err(|e| e.with_token(token))
     }
 
     fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -97,9 +155,8 @@ impl<'de, 'a, Reader: EsoReaderTrait<'de>> de::Deserializer<'de> for &'a mut Des
         let result = match self.next()? {
             "T" => true,
             "F" => false,
-            _ => {
-                // println!("{other}");
-                return Err(Error::InvalidToken);
+            other => {
+                return Err(Error::from(ErrorKind::InvalidToken).with_token(other));
             },
         };
 
@@ -189,7 +246,8 @@ impl<'de, 'a, Reader: EsoReaderTrait<'de>> de::Deserializer<'de> for &'a mut Des
     {
         let string = self.next()?;
         
-        visitor.visit_borrowed_str(string)
+        visit_str_token(string, visitor)
+            .map_err(|e: Error| e.with_token(string))
     }
 
     fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -222,6 +280,7 @@ impl<'de, 'a, Reader: EsoReaderTrait<'de>> de::Deserializer<'de> for &'a mut Des
         } else {
             let mut copy = Deserializer {
                 s: self.s.clone(),
+                last_token: self.last_token,
                 _phantom: Default::default(),
             };
 
@@ -295,14 +354,24 @@ impl<'de, 'a, Reader: EsoReaderTrait<'de>> de::Deserializer<'de> for &'a mut Des
 
     fn deserialize_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_seq(SeqWrapper::new(self, fields.len()))
+        match name {
+            SLASH_SEPARATED => {
+                let token = self.next()?;
+
+                // tokens are short, plain split is faster than the unguarded one
+                visitor.visit_seq(PairWrapper::<EsoLogReader>(EsoLogReader::new_split(token, '/')))
+                    .map_err(|e| e.with_token(token))
+            },
+            BRACKETED => visitor.visit_seq(VecWrapper::<Reader>::new(self.next()?)?),
+            _ => visitor.visit_seq(SeqWrapper::with_fields(self, fields)),
+        }
     }
 
     fn deserialize_enum<V>(
@@ -321,7 +390,7 @@ impl<'de, 'a, Reader: EsoReaderTrait<'de>> de::Deserializer<'de> for &'a mut Des
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::UnsupportedOperation("Map deserialization is not supported by this deserializer"))
+        Err(ErrorKind::UnsupportedOperation("Map deserialization is not supported by this deserializer").into())
     }
 
     fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -339,6 +408,153 @@ impl<'de, 'a, Reader: EsoReaderTrait<'de>> de::Deserializer<'de> for &'a mut Des
     }
 }
 
+/// visit string token, quoted strings are visited without the quotes,
+/// and with escaped `""` replaced by `"`
+#[inline]
+fn visit_str_token<'de, V>(token: &'de str, visitor: V) -> Result<V::Value, Error>
+where
+    V: de::Visitor<'de>,
+{
+    let s = match token.as_bytes() {
+        [b'"', .., b'"'] => &token[1..token.len() - 1],
+        _ => return visitor.visit_borrowed_str(token),
+    };
+
+    if crate::compat::likely(!s.contains('"')) {
+        visitor.visit_borrowed_str(s)
+    } else {
+        visitor.visit_string(s.replace("\"\"", "\""))
+    }
+}
+
+/// infer type of the token:
+/// - `T`/`F` as bool
+/// - `*` as none
+/// - `"..."` as string, without the quotes
+/// - `[...]` as sequence
+/// - integers and floats
+/// - `a/b` with numeric `a` and `b` as sequence of two numbers
+/// - everything else as string
+fn visit_token<'de, V, Reader>(token: &'de str, visitor: V) -> Result<V::Value, Error>
+where
+    V: de::Visitor<'de>,
+    Reader: EsoReaderTrait<'de>,
+{
+    let bytes = token.as_bytes();
+
+    match bytes {
+        b"T" => return visitor.visit_bool(true),
+        b"F" => return visitor.visit_bool(false),
+        b"*" => return visitor.visit_none(),
+        [b'"', .., b'"'] => return visit_str_token(token, visitor),
+        [b'[', .., b']'] => return visitor.visit_seq(VecWrapper::<Reader>::new(token)?),
+        _ => { },
+    }
+
+    if is_number(token) {
+        if let Ok(v) = token.parse::<u64>() {
+            return visitor.visit_u64(v);
+        }
+
+        if let Ok(v) = token.parse::<i64>() {
+            return visitor.visit_i64(v);
+        }
+
+        if let Ok(v) = token.parse::<f64>() {
+            return visitor.visit_f64(v);
+        }
+    }
+
+    if token.split_once('/').is_some_and(|(a, b)| is_number(a) && is_number(b)) {
+        return visitor.visit_seq(PairWrapper::<Reader>(Reader::new_split(token, '/')));
+    }
+
+    visitor.visit_borrowed_str(token)
+}
+
+/// whether token is an integer or a float, eg. `-12` or `0.5`
+fn is_number(token: &str) -> bool {
+    let bytes = token.as_bytes();
+    let digits = bytes.strip_prefix(b"-").unwrap_or(bytes);
+
+    digits.first().is_some_and(u8::is_ascii_digit)
+        && digits.iter().all(|&c| c.is_ascii_digit() || c == b'.')
+}
+
+macro_rules! deserialize_token_number {
+    ($($fn:ident => $visit:ident),+ $(,)?) => {
+        $(
+            fn $fn<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: de::Visitor<'de>,
+            {
+                let (number, _) = NumberParser::parse(self.0.as_bytes())
+                    .map_err(|e: Error| e.with_token(self.0))?;
+
+                visitor.$visit(number)
+            }
+        )+
+    };
+}
+
+impl<'de, Reader: EsoReaderTrait<'de>> de::Deserializer<'de> for TokenDeserializer<'de, Reader> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visit_token::<V, Reader>(self.0, visitor)
+    }
+
+    // integers are parsed directly, they're the most common values of `a/b` tokens
+    deserialize_token_number! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+    }
+
+    forward_to_deserialize_any! {
+        bool i128 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, 'a, Reader: EsoReaderTrait<'de>> SeqAccess<'de> for LineWrapper<'de, 'a, Reader> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if !self.0.is_depleted() {
+            seed.deserialize(&mut *self.0).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'de, Reader: EsoReaderTrait<'de>> SeqAccess<'de> for PairWrapper<'de, Reader> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.0.next() {
+            Some(token) => seed.deserialize(TokenDeserializer::<Reader>(token, PhantomData)).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
 impl<'de, Reader: EsoReaderTrait<'de>> SeqAccess<'de> for VecWrapper<'de, Reader> {
     type Error = Error;
 
@@ -361,7 +577,22 @@ impl<'de, 'a, Reader: EsoReaderTrait<'de>> SeqAccess<'de> for SeqWrapper<'de, 'a
     where
         T: de::DeserializeSeed<'de>,
     {
-        seed.deserialize(&mut *self.0).map(Some)
+        let index = self.index;
+        self.index += 1;
+
+        seed.deserialize(&mut *self.de)
+            .map(Some)
+            .map_err(|mut e| {
+                // errors raised by `Deserialize` implementations after reading the token
+                if let ErrorKind::CustomError(_) = e.kind() {
+                    e = e.with_token(self.de.last_token);
+                }
+
+                match self.fields.get(index) {
+                    Some(name) => e.with_field(index + 1, name),
+                    None => e,
+                }
+            })
     }
 }
 
@@ -373,9 +604,10 @@ impl<'de, 'a, Reader: EsoReaderTrait<'de>> EnumAccess<'de> for Enum<'de, 'a, Rea
     where
         V: de::DeserializeSeed<'de>,
     {
-        let val = seed.deserialize(&mut *self.0)?;
+        let val = seed.deserialize(&mut *self.de)?;
+        let variant = self.de.last_token;
 
-        Ok((val, self))
+        Ok((val, Self { variant, ..self }))
     }
 }
 
@@ -390,7 +622,10 @@ impl<'de, 'a, Reader: EsoReaderTrait<'de>> VariantAccess<'de> for Enum<'de, 'a,
     where
         T: de::DeserializeSeed<'de>,
     {
-        seed.deserialize(self.0)
+        let variant = self.variant;
+
+        seed.deserialize(self.de)
+            .map_err(|e| e.with_event(variant))
     }
 
     fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
@@ -408,6 +643,6 @@ impl<'de, 'a, Reader: EsoReaderTrait<'de>> VariantAccess<'de> for Enum<'de, 'a,
     where
         V: de::Visitor<'de>,
     {
-        de::Deserializer::deserialize_struct(&mut *self.0, "", fields, visitor)
+        de::Deserializer::deserialize_struct(&mut *self.de, "", fields, visitor)
     }
 }
@@ -1,10 +1,9 @@
-
-use std::num::{ParseIntError, ParseFloatError};
+use std::{fmt, num::{ParseIntError, ParseFloatError}, str::Utf8Error};
 
 use serde::{de, ser};
 
 #[derive(Debug, derive_more::Display)]
-pub enum Error {
+pub enum ErrorKind {
     InvalidToken,
     UnexpectedEnd,
     ParseIntError,
@@ -12,35 +11,214 @@ pub enum Error {
     NotAList,
     ReaderNotExhausted,
     CustomError(String),
+    #[display(fmt = "unknown variant `{}`", _0)]
+    UnknownVariant(String),
     UnsupportedOperation(&'static str),
+    IoError(std::io::Error),
+    Utf8Error(Utf8Error),
+    /// binary stream doesn't start with expected magic and version
+    InvalidHeader,
+}
+
+/// Deserialization error, with informations about where it occurred
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    // boxed to keep `Result`s small on the happy path
+    context: Option<Box<ErrorContext>>,
+}
+
+/// Informations about where an error occurred, every field is filled only if it is known
+#[derive(Debug, Default, Clone)]
+pub struct ErrorContext {
+    line: Option<usize>,
+    offset: Option<u64>,
+    event: Option<String>,
+    field: Option<(usize, &'static str)>,
+    token: Option<String>,
+}
+
+impl Error {
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    pub fn context(&self) -> Option<&ErrorContext> {
+        self.context.as_deref()
+    }
+
+    /// 1-based line number
+    pub fn line(&self) -> Option<usize> {
+        self.context()?.line
+    }
+
+    /// offset in bytes of the line start, from the beginning of the log
+    pub fn offset(&self) -> Option<u64> {
+        self.context()?.offset
+    }
+
+    /// name of the event that failed to parse, eg. `COMBAT_EVENT`
+    pub fn event(&self) -> Option<&str> {
+        self.context()?.event.as_deref()
+    }
+
+    /// 1-based index of the event field that failed to parse
+    pub fn field_index(&self) -> Option<usize> {
+        self.context()?.field.map(|(index, _)| index)
+    }
+
+    /// name of the event field that failed to parse
+    pub fn field_name(&self) -> Option<&'static str> {
+        self.context()?.field.map(|(_, name)| name)
+    }
+
+    /// token that failed to parse
+    pub fn token(&self) -> Option<&str> {
+        self.context()?.token.as_deref()
+    }
+
+    /// whether this error was caused by an event type unknown to the parser
+    pub fn is_unknown_event(&self) -> bool {
+        matches!(self.kind, ErrorKind::UnknownVariant(_))
+        && self.event().is_none()
+    }
+
+    /// attach position of the line that failed to parse, if it's not already known
+    pub fn with_position(mut self, line: usize, offset: u64) -> Self {
+        let context = self.context_mut();
+        context.line.get_or_insert(line);
+        context.offset.get_or_insert(offset);
+
+        self
+    }
+
+    /// attach name of the event that failed to parse, if it's not already known
+    pub fn with_event(mut self, event: &str) -> Self {
+        let context = self.context_mut();
+        if context.event.is_none() {
+            context.event = Some(event.to_owned());
+        }
+
+        self
+    }
+
+    /// attach field, unless error already went through an event,
+    /// so that outermost field of the event is reported
+    pub(crate) fn with_field(mut self, index: usize, name: &'static str) -> Self {
+        let context = self.context_mut();
+        if context.event.is_none() {
+            context.field = Some((index, name));
+        }
+
+        self
+    }
+
+    /// attach token that failed to parse, if it's not already known
+    pub fn with_token(mut self, token: &str) -> Self {
+        let context = self.context_mut();
+        if context.token.is_none() {
+            context.token = Some(token.to_owned());
+        }
+
+        self
+    }
+
+    fn context_mut(&mut self) -> &mut ErrorContext {
+        self.context.get_or_insert_with(Default::default)
+    }
 }
 
-impl ser::StdError for Error {}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(context) = self.context() else {
+            return write!(f, "{}", self.kind);
+        };
+
+        if let Some(line) = context.line {
+            write!(f, "line {line}")?;
+            if let Some(offset) = context.offset {
+                write!(f, " (byte {offset})")?;
+            }
+            write!(f, ": ")?;
+        }
+
+        if let Some(event) = &context.event {
+            write!(f, "{event} ")?;
+        }
+
+        if let Some((index, name)) = context.field {
+            write!(f, "field {index} {name}: ")?;
+        }
+
+        write!(f, "{}", self.kind)?;
+
+        if let Some(token) = &context.token {
+            write!(f, " (token `{token}`)")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ser::StdError for Error {
+    fn source(&self) -> Option<&(dyn ser::StdError + 'static)> {
+        match &self.kind {
+            ErrorKind::IoError(e) => Some(e),
+            ErrorKind::Utf8Error(e) => Some(e),
+            _ => None,
+        }
+    }
+}
 
 impl de::Error for Error {
     fn custom<T>(msg: T) -> Self
     where
         T: std::fmt::Display,
     {
-        Self::CustomError(format!("{}", msg))
+        ErrorKind::CustomError(format!("{}", msg)).into()
+    }
+
+    fn unknown_variant(variant: &str, _expected: &'static [&'static str]) -> Self {
+        Error::from(ErrorKind::UnknownVariant(variant.to_owned())).with_token(variant)
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            context: None,
+        }
     }
 }
 
 impl From<ParseIntError> for Error {
     fn from(_: ParseIntError) -> Self {
-        Self::ParseIntError
+        ErrorKind::ParseIntError.into()
     }
 }
 
 impl From<ParseFloatError> for Error {
     fn from(_: ParseFloatError) -> Self {
-        Self::ParseFloatError
+        ErrorKind::ParseFloatError.into()
     }
 }
 
 impl From<fast_float::Error> for Error {
     fn from(_: fast_float::Error) -> Self {
-        Self::ParseFloatError
+        ErrorKind::ParseFloatError.into()
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        ErrorKind::IoError(e).into()
+    }
+}
+
+impl From<Utf8Error> for Error {
+    fn from(e: Utf8Error) -> Self {
+        ErrorKind::Utf8Error(e).into()
     }
 }
 
@@ -48,9 +226,23 @@ impl From<fast_float::Error> for Error {
 pub enum SerializeError {
     CustomError(String),
     UnsupportedOperation(&'static str),
+    IoError(std::io::Error),
+}
+
+impl ser::StdError for SerializeError {
+    fn source(&self) -> Option<&(dyn ser::StdError + 'static)> {
+        match self {
+            Self::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
-impl ser::StdError for SerializeError { }
+impl From<std::io::Error> for SerializeError {
+    fn from(e: std::io::Error) -> Self {
+        Self::IoError(e)
+    }
+}
 
 impl ser::Error for SerializeError {
     fn custom<T>(msg: T) -> Self
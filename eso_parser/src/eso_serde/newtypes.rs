@@ -46,3 +46,37 @@ impl Serialize for EsoSystemTime {
         EsoDuration(self.0.duration_since(SystemTime::UNIX_EPOCH).unwrap()).serialize(serializer)
     }
 }
+
+/// Token written to the log as-is, without quotes and escaping applied to strings,
+/// other serializers see it as a regular string
+#[derive(Debug, Clone, Copy)]
+pub struct RawToken<'a>(pub &'a str);
+
+pub(crate) const RAW_TOKEN: &str = "$eso_serde::RawToken";
+
+impl Serialize for RawToken<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_newtype_struct(RAW_TOKEN, self.0)
+    }
+}
+
+/// Name of the struct written to the log as a single token, with its fields separated by `/`, eg. `current/max`,
+/// other formats see it as a regular struct
+///
+/// apply it to the struct with `#[serde(rename = "$eso_serde::SlashSeparated")]`
+pub const SLASH_SEPARATED: &str = "$eso_serde::SlashSeparated";
+
+/// Name of the struct written to the log as a list of its fields, eg. `[a,b,c]`,
+/// other formats see it as a regular struct
+///
+/// apply it to the struct with `#[serde(rename = "$eso_serde::Bracketed")]`
+pub const BRACKETED: &str = "$eso_serde::Bracketed";
+
+/// Name of the newtype over `Option`, whose `None` is written to the log as `*` even when no field follows it,
+/// other formats see it as a regular `Option`
+///
+/// apply it to the newtype with `#[serde(rename = "$eso_serde::ExplicitNone")]`
+pub const EXPLICIT_NONE: &str = "$eso_serde::ExplicitNone";
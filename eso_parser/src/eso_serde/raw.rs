@@ -0,0 +1,178 @@
+use std::{borrow::Cow, fmt};
+
+use serde::{de, Deserialize, Serialize};
+
+use super::newtypes::EXPLICIT_NONE;
+
+/// Value of any field, with type inferred from its token
+///
+/// `a/b` pairs are represented as `List` of two values
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawValue<'a> {
+    None,
+    Bool(bool),
+    Int(i64),
+    /// integers too big for `i64`
+    UInt(u64),
+    Float(f64),
+    Str(Cow<'a, str>),
+    List(Vec<RawValue<'a>>),
+}
+
+impl<'a> RawValue<'a> {
+    /// convert into value that doesn't borrow from the parsed data
+    pub fn into_owned(self) -> RawValue<'static> {
+        match self {
+            Self::None => RawValue::None,
+            Self::Bool(v) => RawValue::Bool(v),
+            Self::Int(v) => RawValue::Int(v),
+            Self::UInt(v) => RawValue::UInt(v),
+            Self::Float(v) => RawValue::Float(v),
+            Self::Str(v) => RawValue::Str(Cow::Owned(v.into_owned())),
+            Self::List(v) => RawValue::List(v.into_iter().map(RawValue::into_owned).collect()),
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Int(v) => Some(*v),
+            Self::UInt(v) => (*v).try_into().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::Int(v) => (*v).try_into().ok(),
+            Self::UInt(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// value as float, integers are converted
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Int(v) => Some(*v as f64),
+            Self::UInt(v) => Some(*v as f64),
+            Self::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Str(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[RawValue<'a>]> {
+        match self {
+            Self::List(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for RawValue<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(RawValueVisitor)
+    }
+}
+
+impl Serialize for RawValue<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            // read from `*`, so it's written back even without fields following it
+            Self::None => serializer.serialize_newtype_struct(EXPLICIT_NONE, &None::<()>),
+            Self::Bool(v) => serializer.serialize_bool(*v),
+            Self::Int(v) => serializer.serialize_i64(*v),
+            Self::UInt(v) => serializer.serialize_u64(*v),
+            Self::Float(v) => serializer.serialize_f64(*v),
+            Self::Str(v) => serializer.serialize_str(v),
+            Self::List(v) => v.serialize(serializer),
+        }
+    }
+}
+
+struct RawValueVisitor;
+
+impl<'de> de::Visitor<'de> for RawValueVisitor {
+    type Value = RawValue<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(RawValue::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(RawValue::Int(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(v.try_into().map_or(RawValue::UInt(v), RawValue::Int))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(RawValue::Float(v))
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(RawValue::Str(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(RawValue::Str(Cow::Owned(v.to_owned())))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(RawValue::Str(Cow::Owned(v)))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(RawValue::None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(RawValue::None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        RawValue::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+
+        Ok(RawValue::List(values))
+    }
+}
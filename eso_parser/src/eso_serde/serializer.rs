@@ -1,94 +1,196 @@
-use super::SerializeError;
-use serde::{ser, Serialize};
+use std::io::{self, Write};
+
+use super::{newtypes::{BRACKETED, EXPLICIT_NONE, RAW_TOKEN, SLASH_SEPARATED}, SerializeError};
+use serde::ser;
+
+/// Serializes values into the log format, writing them straight to `W`
+///
+/// - strings are quoted, with `"` escaped as `""`
+/// - floats are written with 4 decimal places, like the game does
+/// - `None` is written as `*`, unless no other field follows it
+/// - structs are written field after field, unless named `newtypes::SLASH_SEPARATED` or `newtypes::BRACKETED`
+///
+/// writes are small and frequent, so `W` should be buffered, eg. with `BufWriter`
+pub struct Serializer<W = Vec<u8>> {
+    out: W,
+    // whether `,` has to be written before the next field
+    needs_separator: bool,
+    // `None`s are written only when followed by another field,
+    // so optional trailing fields are omitted
+    pending_nones: usize,
+    raw_token: bool,
+    // written between fields, `/` inside of `SLASH_SEPARATED` structs
+    separator: u8,
+    // next `None` is written right away, set by `EXPLICIT_NONE` newtypes
+    explicit_none: bool,
+}
 
-pub struct Serializer {
-    out: String,
+impl Default for Serializer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Serializer {
+    /// create serializer writing into in-memory buffer
     pub fn new() -> Self {
-        Self {
-            out: String::with_capacity(128)
-        }
+        Self::from_writer(Vec::with_capacity(128))
     }
 
     pub fn as_str(&self) -> &str {
-        let mut chars = self.out.chars();
-        chars.next_back();
+        // SAFETY: only valid UTF-8 strings are written into the buffer
+        unsafe { std::str::from_utf8_unchecked(&self.out) }
+    }
+
+    pub fn into_string(self) -> String {
+        // SAFETY: only valid UTF-8 strings are written into the buffer
+        unsafe { String::from_utf8_unchecked(self.out) }
+    }
+}
 
-        chars.as_str()
+impl<W: Write> Serializer<W> {
+    pub fn from_writer(out: W) -> Self {
+        Self {
+            out,
+            needs_separator: false,
+            pending_nones: 0,
+            raw_token: false,
+            separator: b',',
+            explicit_none: false,
+        }
+    }
+
+    pub fn writer(&self) -> &W {
+        &self.out
     }
 
-    pub fn into_string(mut self) -> String {
-        self.out.pop();
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.out
+    }
 
+    pub fn into_inner(self) -> W {
         self.out
     }
 
-    fn push_str(&mut self, s: &str) -> Result<(), SerializeError> {
-        self.out.push_str(s);
-        self.out.push(',');
+    /// terminate current line, next value is written as the first field of a new line
+    pub fn end_line(&mut self) -> Result<(), SerializeError> {
+        self.needs_separator = false;
+        self.pending_nones = 0;
+        self.out.write_all(b"\n")?;
 
         Ok(())
     }
 
+    /// write `,` separating value from the previous one, preceded by postponed `None`s
     #[inline]
-    fn serialize_unsigned(&mut self, mut i: u64) -> Result<(), SerializeError> {
-        if i == 0 {
-            self.out.push_str("0,");
-            return Ok(());
-        }
-
-        let buf = unsafe { self.out.as_mut_vec() };
-        let start = buf.len();
-
-        while i != 0 {
-            let rem = (i % 10) as u8;
-            i /= 10;
+    fn begin_field(&mut self) -> io::Result<()> {
+        for _ in 0..std::mem::take(&mut self.pending_nones) {
+            if self.needs_separator {
+                self.out.write_all(&[self.separator])?;
+            }
 
-            buf.push(b'0' + rem);
+            self.out.write_all(b"*")?;
+            self.needs_separator = true;
         }
 
-        unsafe {
-            buf.split_at_mut_unchecked(start).1.reverse();
+        if self.needs_separator {
+            self.out.write_all(&[self.separator])?;
         }
 
-        buf.push(b',');
+        self.needs_separator = true;
+        Ok(())
+    }
+
+    fn push_str(&mut self, s: &str) -> Result<(), SerializeError> {
+        self.begin_field()?;
+        self.out.write_all(s.as_bytes())?;
+
         Ok(())
     }
 
     #[inline]
-    fn serialize_signed(&mut self, mut i: i64) -> Result<(), SerializeError> {
-        if i == 0 {
-            self.out.push_str("0,");
-            return Ok(());
-        }
+    fn serialize_unsigned(&mut self, i: u64) -> Result<(), SerializeError> {
+        self.begin_field()?;
+        self.write_digits(i)
+    }
 
+    #[inline]
+    fn serialize_signed(&mut self, i: i64) -> Result<(), SerializeError> {
+        self.begin_field()?;
         if i < 0 {
-            self.out.push('-');
+            self.out.write_all(b"-")?;
         }
 
-        let buf = unsafe { self.out.as_mut_vec() };
-        let start = buf.len();
-        buf.reserve(20);
+        self.write_digits(i.unsigned_abs())
+    }
 
-        while i != 0 {
-            let rem = (i % 10) as u8;
+    #[inline]
+    fn write_digits(&mut self, mut i: u64) -> Result<(), SerializeError> {
+        // u64::MAX has 20 digits
+        let mut buf = [0u8; 20];
+        let mut start = buf.len();
+
+        loop {
+            start -= 1;
+            buf[start] = b'0' + (i % 10) as u8;
             i /= 10;
 
-            buf.push(b'0' + rem);
+            if i == 0 { break; }
+        }
+
+        self.out.write_all(&buf[start..])?;
+        Ok(())
+    }
+
+    fn serialize_float(&mut self, v: f64) -> Result<(), SerializeError> {
+        self.begin_field()?;
+        write!(self.out, "{v:.4}")?;
+
+        Ok(())
+    }
+
+    fn serialize_quoted(&mut self, s: &str) -> Result<(), SerializeError> {
+        self.begin_field()?;
+        self.out.write_all(b"\"")?;
+
+        let mut parts = s.split('"');
+        if let Some(part) = parts.next() {
+            self.out.write_all(part.as_bytes())?;
         }
 
-        unsafe {
-            buf.split_at_mut_unchecked(start).1.reverse();
+        for part in parts {
+            self.out.write_all(b"\"\"")?;
+            self.out.write_all(part.as_bytes())?;
         }
 
-        buf.push(b',');
+        self.out.write_all(b"\"")?;
+        Ok(())
+    }
+
+    fn open_seq(&mut self) -> Result<(), SerializeError> {
+        self.begin_field()?;
+        self.out.write_all(b"[")?;
+        self.needs_separator = false;
+
         Ok(())
     }
 }
 
-impl<'a> serde::Serializer for &'a mut Serializer {
+/// Layout of the struct in the log, selected by its name
+#[derive(Debug, Clone, Copy)]
+enum StructLayout {
+    Inline,
+    Bracketed,
+    SlashSeparated,
+}
+
+/// Serializes fields of the struct, returned by `serialize_struct`
+pub struct StructSerializer<'a, W> {
+    ser: &'a mut Serializer<W>,
+    layout: StructLayout,
+}
+
+impl<'a, W: Write> serde::Serializer for &'a mut Serializer<W> {
     type Ok = ();
     type Error = SerializeError;
 
@@ -96,7 +198,7 @@ impl<'a> serde::Serializer for &'a mut Serializer {
     type SerializeTuple = Self;
     type SerializeTupleStruct = Self;
     type SerializeTupleVariant = Self;
-    type SerializeStruct = Self;
+    type SerializeStruct = StructSerializer<'a, W>;
     type SerializeStructVariant = Self;
     type SerializeMap = Self;
 
@@ -138,23 +240,25 @@ impl<'a> serde::Serializer for &'a mut Serializer {
     }
 
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
-        
-        self.push_str(&v.to_string())
+        self.serialize_float(v as _)
     }
 
     fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
-        
-        self.push_str(&v.to_string())
+        self.serialize_float(v)
     }
 
     fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
         let mut buf = [0; 4];
 
-        self.push_str(v.encode_utf8(&mut buf))
+        self.serialize_str(v.encode_utf8(&mut buf))
     }
 
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
-        self.push_str(v)
+        if self.raw_token {
+            self.push_str(v)
+        } else {
+            self.serialize_quoted(v)
+        }
     }
 
     fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
@@ -162,13 +266,20 @@ impl<'a> serde::Serializer for &'a mut Serializer {
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        if std::mem::take(&mut self.explicit_none) {
+            return self.push_str("*");
+        }
+
+        self.pending_nones += 1;
+
         Ok(())
     }
 
-    fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: serde::Serialize,
+        T: ?Sized + serde::Serialize,
     {
+        self.explicit_none = false;
         value.serialize(self)
     }
 
@@ -190,18 +301,34 @@ impl<'a> serde::Serializer for &'a mut Serializer {
         self.serialize_unit()
     }
 
-    fn serialize_newtype_struct<T: ?Sized>(
+    fn serialize_newtype_struct<T>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
-        T: serde::Serialize,
+        T: ?Sized + serde::Serialize,
     {
-        value.serialize(self)
+        match name {
+            RAW_TOKEN => {
+                self.raw_token = true;
+                let result = value.serialize(&mut *self);
+                self.raw_token = false;
+
+                result
+            },
+            EXPLICIT_NONE => {
+                self.explicit_none = true;
+                let result = value.serialize(&mut *self);
+                self.explicit_none = false;
+
+                result
+            },
+            _ => value.serialize(self),
+        }
     }
 
-    fn serialize_newtype_variant<T: ?Sized>(
+    fn serialize_newtype_variant<T>(
         self,
         _name: &'static str,
         _variant_index: u32,
@@ -209,14 +336,14 @@ impl<'a> serde::Serializer for &'a mut Serializer {
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
-        T: serde::Serialize,
+        T: ?Sized + serde::Serialize,
     {
-        variant.serialize(&mut *self)?;
+        self.push_str(variant)?;
         value.serialize(self)
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        self.out.push('[');
+        self.open_seq()?;
 
         Ok(self)
     }
@@ -227,7 +354,7 @@ impl<'a> serde::Serializer for &'a mut Serializer {
         _len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error>
     {
-        self.out.push('[');
+        self.open_seq()?;
 
         Ok(self)
     }
@@ -245,11 +372,25 @@ impl<'a> serde::Serializer for &'a mut Serializer {
 
     fn serialize_struct(
         self,
-        _name: &'static str,
+        name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error>
     {
-        Ok(self)
+        let layout = match name {
+            BRACKETED => {
+                self.open_seq()?;
+                StructLayout::Bracketed
+            },
+            SLASH_SEPARATED => {
+                self.begin_field()?;
+                self.needs_separator = false;
+                self.separator = b'/';
+                StructLayout::SlashSeparated
+            },
+            _ => StructLayout::Inline,
+        };
+
+        Ok(StructSerializer { ser: self, layout })
     }
 
     fn serialize_struct_variant(
@@ -264,7 +405,7 @@ impl<'a> serde::Serializer for &'a mut Serializer {
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        self.out.push('[');
+        self.open_seq()?;
 
         Ok(self)
     }
@@ -274,41 +415,34 @@ impl<'a> serde::Serializer for &'a mut Serializer {
     }
 }
 
-impl<'a> ser::SerializeSeq for &'a mut Serializer {
+impl<W: Write> ser::SerializeSeq for &mut Serializer<W> {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        T: serde::Serialize,
+        T: ?Sized + serde::Serialize,
     {
         value.serialize(&mut **self)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        if self.out.as_bytes().last() == Some(&b',') {
-            unsafe {
-                *self.out
-                    .as_bytes_mut()
-                    .last_mut()
-                    .unwrap_unchecked() = b']';
-            }
-        } else {
-            self.out.push(']');
-        }
+        // nones trailing inside of the list are omitted, same as at the end of the line
+        self.pending_nones = 0;
+        self.out.write_all(b"]")?;
+        self.needs_separator = true;
 
-        self.out.push(',');
         Ok(())
     }
 }
 
-impl<'a> ser::SerializeTuple for &'a mut Serializer {
+impl<W: Write> ser::SerializeTuple for &mut Serializer<W> {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        T: serde::Serialize,
+        T: ?Sized + serde::Serialize,
     {
         ser::SerializeSeq::serialize_element(self, value)
     }
@@ -318,13 +452,13 @@ impl<'a> ser::SerializeTuple for &'a mut Serializer {
     }
 }
 
-impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
+impl<W: Write> ser::SerializeTupleStruct for &mut Serializer<W> {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        T: serde::Serialize,
+        T: ?Sized + serde::Serialize,
     {
         ser::SerializeSeq::serialize_element(self, value)
     }
@@ -334,13 +468,13 @@ impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
     }
 }
 
-impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
+impl<W: Write> ser::SerializeTupleVariant for &mut Serializer<W> {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        T: serde::Serialize,
+        T: ?Sized + serde::Serialize,
     {
         ser::SerializeSeq::serialize_element(self, value)
     }
@@ -350,37 +484,47 @@ impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
     }
 }
 
-impl<'a> ser::SerializeStruct for &'a mut Serializer {
+impl<W: Write> ser::SerializeStruct for StructSerializer<'_, W> {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_field<T: ?Sized>(
+    fn serialize_field<T>(
         &mut self,
         _key: &'static str,
         value: &T,
     ) -> Result<(), Self::Error>
     where
-        T: serde::Serialize,
+        T: ?Sized + serde::Serialize,
     {
-        value.serialize(&mut **self)
+        value.serialize(&mut *self.ser)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Ok(())
+        match self.layout {
+            StructLayout::Inline => Ok(()),
+            StructLayout::Bracketed => ser::SerializeSeq::end(self.ser),
+            StructLayout::SlashSeparated => {
+                self.ser.pending_nones = 0;
+                self.ser.separator = b',';
+                self.ser.needs_separator = true;
+
+                Ok(())
+            },
+        }
     }
 }
 
-impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
+impl<W: Write> ser::SerializeStructVariant for &mut Serializer<W> {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_field<T: ?Sized>(
+    fn serialize_field<T>(
         &mut self,
         _key: &'static str,
         value: &T,
     ) -> Result<(), Self::Error>
     where
-        T: serde::Serialize,
+        T: ?Sized + serde::Serialize,
     {
         value.serialize(&mut **self)
     }
@@ -390,34 +534,34 @@ impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
     }
 }
 
-const MAP_SERIALIZATION_NOT_SUPPORTED: &'static str = "Map serialization is not supported by this serializer";
+const MAP_SERIALIZATION_NOT_SUPPORTED: &str = "Map serialization is not supported by this serializer";
 
-impl<'a> ser::SerializeMap for &'a mut Serializer {
+impl<W: Write> ser::SerializeMap for &mut Serializer<W> {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_entry<K: ?Sized, V: ?Sized>(
+    fn serialize_entry<K, V>(
         &mut self,
         _key: &K,
         _value: &V,
     ) -> Result<(), Self::Error>
     where
-        K: serde::Serialize,
-        V: serde::Serialize,
+        K: ?Sized + serde::Serialize,
+        V: ?Sized + serde::Serialize,
     {
         Err(SerializeError::UnsupportedOperation(MAP_SERIALIZATION_NOT_SUPPORTED))
     }
 
-    fn serialize_key<T: ?Sized>(&mut self, _key: &T) -> Result<(), Self::Error>
+    fn serialize_key<T>(&mut self, _key: &T) -> Result<(), Self::Error>
     where
-        T: serde::Serialize,
+        T: ?Sized + serde::Serialize,
     {
         Err(SerializeError::UnsupportedOperation(MAP_SERIALIZATION_NOT_SUPPORTED))
     }
 
-    fn serialize_value<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
+    fn serialize_value<T>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
-        T: serde::Serialize,
+        T: ?Sized + serde::Serialize,
     {
         Err(SerializeError::UnsupportedOperation(MAP_SERIALIZATION_NOT_SUPPORTED))
     }
@@ -1,14 +1,20 @@
-#![feature(slice_from_ptr_range)]
-#![feature(portable_simd)]
-#![feature(core_intrinsics)]
+#![cfg_attr(feature = "nightly", feature(slice_from_ptr_range))]
+#![cfg_attr(feature = "nightly", feature(portable_simd))]
+#![cfg_attr(feature = "nightly", feature(core_intrinsics))]
+#![cfg_attr(feature = "nightly", allow(internal_features))]
 
 //! Serializer and deserializer implementation for
 //! The Elder Scrolls Online encounter log format
 //! 
 //! You probably don't want to use this, and use `eso_lib` instead
+//! 
+//! Default `nightly` feature enables SIMD and requires nightly toolchain,
+//! without it crate builds on stable, producing exactly the same output
 
+mod compat;
 mod eso_reader;
 pub mod eso_serde;
+pub mod simd_find;
 
 pub use eso_reader::*;
 mod number_parser;
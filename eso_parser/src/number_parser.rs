@@ -1,7 +1,7 @@
 use atoi::{FromRadix10, FromRadix10Signed};
-use crate::eso_serde::Error;
+use crate::eso_serde::{Error, ErrorKind};
 
-use std::intrinsics::likely;
+use crate::compat::likely;
 
 pub(crate) trait NumberParser
 where
@@ -22,7 +22,7 @@ macro_rules! impl_int_parser {
                 if likely((parsed_bytes != 0) & (next_byte == Some(&b',')) | next_byte.is_none()) {
                     Ok((number, parsed_bytes + add))
                 } else {
-                    Err(Error::ParseIntError)
+                    Err(ErrorKind::ParseIntError.into())
                 }
             }
         }
@@ -74,30 +74,30 @@ macro_rules! impl_float_parser {
                         let (decimal_part, decimal_parsed): (u64, _) = FromRadix10::from_radix_10(&s[parsed + 1..]);
                         let parsed_total = parsed + decimal_parsed + 1;
 
-                        // this is funny, with lto enabled FMA is fastest, and /4.0 is the slowest
-                        // but with lto disabled its the opposite, *0.25 is always in between
-                        let final_number = if likely(decimal_parsed == 4) {
-                            // i would expect fma to be faster but it not, 3% regression
-                            (0.25 as Self).mul_add(decimal_part as Self, num)
-                            // / 4.0, and * 0.25 are almost equal, 0.25 is actually a bit slower (1%, weird stuff)
-                            // num + decimal_part as Self * 0.25
-                            // num + decimal_part as Self / 4.0
+                        // sign of the integer part is lost when it's `-0`
+                        let negative = s.first() == Some(&b'-');
+                        // game writes floats with 4 decimal places,
+                        // dividing keeps the result closest to the written value
+                        let fraction = if likely(decimal_parsed == 4) {
+                            decimal_part as Self / 10_000.0
                         } else {
-                            num + decimal_part as Self / decimal_parsed as Self
+                            decimal_part as Self / (10.0 as Self).powi(decimal_parsed as i32)
                         };
 
+                        let final_number = if negative { num - fraction } else { num + fraction };
+
                         let next_byte = s.get(parsed_total);
                         let add = next_byte.is_some() as usize;
                         if (next_byte == Some(&b',')) | next_byte.is_none() {
                             Ok((final_number, parsed_total + add))
                         } else {
-                            Err(Error::ParseIntError)
+                            Err(ErrorKind::ParseFloatError.into())
                         }
                     },
                     Some(b',')|None => {
                         Ok((num, parsed + next_byte.is_some() as usize))
                     },
-                    _ => Err(Error::ParseFloatError),
+                    _ => Err(ErrorKind::ParseFloatError.into()),
                 }
             }
         }
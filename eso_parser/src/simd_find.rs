@@ -0,0 +1,350 @@
+//! Delimiter search used by the readers, with implementation selected at runtime
+//!
+//! Best implementation supported by the CPU is detected on the first search,
+//! and cached as a function pointer, so single binary can run on any machine.
+//! SIMD implementations require `nightly` feature, stable builds search 8 bytes at once (SWAR)
+
+use std::sync::atomic::{AtomicPtr, Ordering};
+#[cfg(feature = "nightly")]
+use std::simd::{cmp::SimdPartialEq, Simd};
+
+use crate::UNGUARDED_PADDING;
+
+/// Instruction set used to search for delimiters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SimdLevel {
+    /// portable byte by byte search
+    Scalar,
+    /// portable search of 8 bytes at once, using bit tricks on `u64`
+    Swar,
+    /// 16 bytes at once, baseline on `aarch64`
+    #[cfg(all(feature = "nightly", target_arch = "aarch64"))]
+    Neon,
+    /// 16 bytes at once, baseline on `x86_64`
+    #[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+    Sse2,
+    /// 32 bytes at once
+    #[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+    Avx2,
+    /// 64 bytes at once, requires `avx512f` and `avx512bw`
+    #[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+    Avx512,
+}
+
+type FindFn = unsafe fn(usize, &[u8], u8) -> usize;
+
+static FIND: AtomicPtr<()> = AtomicPtr::new(detect_find as *mut ());
+static FIND_UNGUARDED: AtomicPtr<()> = AtomicPtr::new(detect_find_unguarded as *mut ());
+
+impl SimdLevel {
+    /// every level, from the slowest one
+    pub const ALL: &'static [Self] = &[
+        Self::Scalar,
+        Self::Swar,
+        #[cfg(all(feature = "nightly", target_arch = "aarch64"))]
+        Self::Neon,
+        #[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+        Self::Sse2,
+        #[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+        Self::Avx2,
+        #[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+        Self::Avx512,
+    ];
+
+    /// best level supported by the running CPU
+    pub fn detect() -> Self {
+        Self::ALL.iter()
+            .rev()
+            .copied()
+            .find(|level| level.is_supported())
+            .unwrap_or(Self::Scalar)
+    }
+
+    /// whether running CPU supports this level
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::Scalar => true,
+            Self::Swar => true,
+            #[cfg(all(feature = "nightly", target_arch = "aarch64"))]
+            Self::Neon => true,
+            #[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+            Self::Sse2 => std::arch::is_x86_feature_detected!("sse2"),
+            #[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+            Self::Avx2 => std::arch::is_x86_feature_detected!("avx2"),
+            #[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+            Self::Avx512 => std::arch::is_x86_feature_detected!("avx512f")
+                && std::arch::is_x86_feature_detected!("avx512bw"),
+        }
+    }
+
+    /// find index of the first `f` in `s`, starting at `head`,
+    /// `s.len()` if there is none
+    ///
+    /// # Panics
+    /// if this level is not supported by the running CPU, or if `head > s.len()`
+    pub fn find(self, head: usize, s: &[u8], f: u8) -> usize {
+        assert!(self.is_supported(), "{self:?} is not supported by this CPU");
+        assert!(head <= s.len());
+
+        // SAFETY: we have just checked for required conditions
+        unsafe { (self.functions().0)(head, s, f) }
+    }
+
+    /// same as `find`, but may read past the end of `s`
+    ///
+    /// # Panics
+    /// if this level is not supported by the running CPU
+    ///
+    /// # SAFETY
+    /// Caller must ensure that `head <= s.len()`,
+    /// and that reading `UNGUARDED_PADDING` bytes past `s` is safe
+    pub unsafe fn find_unguarded(self, head: usize, s: &[u8], f: u8) -> usize {
+        assert!(self.is_supported(), "{self:?} is not supported by this CPU");
+
+        (self.functions().1)(head, s, f)
+    }
+
+    fn functions(self) -> (FindFn, FindFn) {
+        match self {
+            Self::Scalar => (find_scalar, find_scalar),
+            Self::Swar => (find_swar, find_swar_unguarded),
+            #[cfg(all(feature = "nightly", target_arch = "aarch64"))]
+            Self::Neon => (find_simd::<16>, find_simd_unguarded::<16>),
+            #[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+            Self::Sse2 => (x86::find_sse2, x86::find_sse2_unguarded),
+            #[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+            Self::Avx2 => (x86::find_avx2, x86::find_avx2_unguarded),
+            #[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+            Self::Avx512 => (x86::find_avx512, x86::find_avx512_unguarded),
+        }
+    }
+}
+
+/// Amount of bytes searched inline, before calling the detected implementation
+///
+/// most of the tokens are shorter than this, and avoid the call overhead
+const PROBE_LEN: usize = 16;
+
+/// find index of the first `f` in `s`, starting at `head`, `s.len()` if there is none
+///
+/// # SAFETY
+/// Caller must ensure that `head <= s.len()`
+#[inline(always)]
+pub(crate) unsafe fn find(head: usize, s: &[u8], f: u8) -> usize {
+    if s.len() - head < PROBE_LEN {
+        return find_scalar(head, s, f);
+    }
+
+    if let Some(i) = probe(head, s, f) {
+        return head + i;
+    }
+
+    let find: FindFn = std::mem::transmute(FIND.load(Ordering::Relaxed));
+    find(head + PROBE_LEN, s, f)
+}
+
+/// same as `find`, but may read past the end of `s`
+///
+/// # SAFETY
+/// Caller must ensure that `head <= s.len()`,
+/// and that reading `UNGUARDED_PADDING` bytes past `s` is safe
+#[inline(always)]
+pub(crate) unsafe fn find_unguarded(head: usize, s: &[u8], f: u8) -> usize {
+    if let Some(i) = probe(head, s, f) {
+        return (head + i).min(s.len());
+    }
+
+    if s.len() - head <= PROBE_LEN {
+        return s.len();
+    }
+
+    let find: FindFn = std::mem::transmute(FIND_UNGUARDED.load(Ordering::Relaxed));
+    find(head + PROBE_LEN, s, f)
+}
+
+/// index of the first `f` in `PROBE_LEN` bytes starting at `head`, relative to `head`
+///
+/// # SAFETY
+/// Caller must ensure that reading `PROBE_LEN` bytes starting at `head` is safe
+#[cfg(feature = "nightly")]
+#[inline(always)]
+unsafe fn probe(head: usize, s: &[u8], f: u8) -> Option<usize> {
+    let values = s.as_ptr().add(head).cast::<Simd<u8, PROBE_LEN>>().read_unaligned();
+    let result = values.simd_eq(Simd::splat(f)).to_bitmask();
+
+    (result != 0).then(|| result.trailing_zeros() as usize)
+}
+
+/// index of the first `f` in `PROBE_LEN` bytes starting at `head`, relative to `head`
+///
+/// # SAFETY
+/// Caller must ensure that reading `PROBE_LEN` bytes starting at `head` is safe
+#[cfg(not(feature = "nightly"))]
+#[inline(always)]
+unsafe fn probe(head: usize, s: &[u8], f: u8) -> Option<usize> {
+    (0..PROBE_LEN)
+        .step_by(WORD_LEN)
+        .find_map(|i| swar_first(read_word(s, head + i), f).map(|j| i + j))
+}
+
+unsafe fn detect_find(head: usize, s: &[u8], f: u8) -> usize {
+    let find = SimdLevel::detect().functions().0;
+    FIND.store(find as *mut (), Ordering::Relaxed);
+
+    find(head, s, f)
+}
+
+unsafe fn detect_find_unguarded(head: usize, s: &[u8], f: u8) -> usize {
+    let find = SimdLevel::detect().functions().1;
+    FIND_UNGUARDED.store(find as *mut (), Ordering::Relaxed);
+
+    find(head, s, f)
+}
+
+/// # SAFETY
+/// Caller must ensure that `head <= s.len()`
+#[inline]
+unsafe fn find_scalar(head: usize, s: &[u8], f: u8) -> usize {
+    for i in head..s.len() {
+        if *s.get_unchecked(i) == f {
+            return i;
+        }
+    }
+
+    s.len()
+}
+
+const WORD_LEN: usize = std::mem::size_of::<u64>();
+const LOW_BITS: u64 = u64::from_ne_bytes([0x01; WORD_LEN]);
+const HIGH_BITS: u64 = u64::from_ne_bytes([0x80; WORD_LEN]);
+
+/// # SAFETY
+/// Caller must ensure that reading `WORD_LEN` bytes starting at `i` is safe
+#[inline(always)]
+unsafe fn read_word(s: &[u8], i: usize) -> u64 {
+    // little endian, so that the first byte is the least significant one
+    u64::from_le_bytes(s.as_ptr().add(i).cast::<[u8; WORD_LEN]>().read_unaligned())
+}
+
+/// index of the first byte of `word` equal to `f`
+#[inline(always)]
+fn swar_first(word: u64, f: u8) -> Option<usize> {
+    // bytes equal to `f` become 0, and get their highest bit set by the subtraction
+    // borrow can mark bytes past the first match, but never the ones before it
+    let x = word ^ (LOW_BITS * f as u64);
+    let result = x.wrapping_sub(LOW_BITS) & !x & HIGH_BITS;
+
+    (result != 0).then(|| result.trailing_zeros() as usize / 8)
+}
+
+/// # SAFETY
+/// Caller must ensure that `head <= s.len()`
+unsafe fn find_swar(mut head: usize, s: &[u8], f: u8) -> usize {
+    while s.len() - head >= WORD_LEN {
+        if let Some(i) = swar_first(read_word(s, head), f) {
+            return head + i;
+        }
+
+        head += WORD_LEN;
+    }
+
+    find_scalar(head, s, f)
+}
+
+/// # SAFETY
+/// Caller must ensure that `head <= s.len()`,
+/// and that reading `WORD_LEN - 1` bytes past `s` is safe
+unsafe fn find_swar_unguarded(mut head: usize, s: &[u8], f: u8) -> usize {
+    const { assert!(WORD_LEN <= UNGUARDED_PADDING) };
+
+    while head < s.len() {
+        if let Some(i) = swar_first(read_word(s, head), f) {
+            return (head + i).min(s.len());
+        }
+
+        head += WORD_LEN;
+    }
+
+    s.len()
+}
+
+/// # SAFETY
+/// Caller must ensure that `head <= s.len()`
+#[cfg(feature = "nightly")]
+#[inline(always)]
+unsafe fn find_simd<const N: usize>(mut head: usize, s: &[u8], f: u8) -> usize {
+    let mask = Simd::<u8, N>::splat(f);
+
+    while s.len() - head >= N {
+        // SAFETY: there is atleast `N` bytes past head that are valid to read
+        let values = s.as_ptr().add(head).cast::<Simd<u8, N>>().read_unaligned();
+        let result = values.simd_eq(mask).to_bitmask();
+
+        if result != 0 {
+            return head + result.trailing_zeros() as usize;
+        }
+
+        head += N;
+    }
+
+    find_scalar(head, s, f)
+}
+
+/// # SAFETY
+/// Caller must ensure that `head <= s.len()`,
+/// and that reading `N - 1` bytes past `s` is safe
+#[cfg(feature = "nightly")]
+#[inline(always)]
+unsafe fn find_simd_unguarded<const N: usize>(mut head: usize, s: &[u8], f: u8) -> usize {
+    const { assert!(N <= UNGUARDED_PADDING) };
+    let mask = Simd::<u8, N>::splat(f);
+
+    while head < s.len() {
+        let values = s.as_ptr().add(head).cast::<Simd<u8, N>>().read_unaligned();
+        let result = values.simd_eq(mask).to_bitmask();
+
+        if result != 0 {
+            return (head + result.trailing_zeros() as usize).min(s.len());
+        }
+
+        head += N;
+    }
+
+    s.len()
+}
+
+/// `find_simd` compiled for specific instruction sets
+#[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+mod x86 {
+    use super::*;
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn find_sse2(head: usize, s: &[u8], f: u8) -> usize {
+        find_simd::<16>(head, s, f)
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn find_sse2_unguarded(head: usize, s: &[u8], f: u8) -> usize {
+        find_simd_unguarded::<16>(head, s, f)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn find_avx2(head: usize, s: &[u8], f: u8) -> usize {
+        find_simd::<32>(head, s, f)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn find_avx2_unguarded(head: usize, s: &[u8], f: u8) -> usize {
+        find_simd_unguarded::<32>(head, s, f)
+    }
+
+    #[target_feature(enable = "avx512f,avx512bw")]
+    pub(super) unsafe fn find_avx512(head: usize, s: &[u8], f: u8) -> usize {
+        find_simd::<64>(head, s, f)
+    }
+
+    #[target_feature(enable = "avx512f,avx512bw")]
+    pub(super) unsafe fn find_avx512_unguarded(head: usize, s: &[u8], f: u8) -> usize {
+        find_simd_unguarded::<64>(head, s, f)
+    }
+}
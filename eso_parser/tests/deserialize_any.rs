@@ -0,0 +1,114 @@
+use std::fmt;
+
+use eso_parser::eso_serde::Deserializer;
+use serde::{de, Deserialize};
+use serde_json::{json, Value};
+
+/// deserialize single token into `Value`
+fn any(token: &str) -> Value {
+    let mut deserializer = Deserializer::new(token);
+    let value = Value::deserialize(&mut deserializer).unwrap();
+
+    assert!(deserializer.is_depleted(), "`{token}` was not fully deserialized");
+    value
+}
+
+/// every field of the line, see `Deserializer::deserialize_line`
+struct Fields;
+
+impl<'de> de::Visitor<'de> for Fields {
+    type Value = Vec<Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("fields of the line")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut fields = Vec::new();
+        while let Some(field) = seq.next_element()? {
+            fields.push(field);
+        }
+
+        Ok(fields)
+    }
+}
+
+#[test]
+fn bools_and_none() {
+    assert_eq!(any("T"), json!(true));
+    assert_eq!(any("F"), json!(false));
+    assert_eq!(any("*"), Value::Null);
+}
+
+#[test]
+fn numbers() {
+    assert_eq!(any("0"), json!(0));
+    assert_eq!(any("12345"), json!(12345));
+    assert_eq!(any("-12"), json!(-12));
+    assert_eq!(any("18446744073709551615"), json!(u64::MAX));
+    assert_eq!(any("0.5099"), json!(0.5099));
+    assert_eq!(any("-4.25"), json!(-4.25));
+}
+
+#[test]
+fn strings() {
+    assert_eq!(any(r#""Z'Maja""#), json!("Z'Maja"));
+    assert_eq!(any(r#""Some, name""#), json!("Some, name"));
+    assert_eq!(any(r#""Say ""hi"" to them""#), json!(r#"Say "hi" to them"#));
+    assert_eq!(any(r#""""#), json!(""));
+    assert_eq!(any("PLAYER_ALLY"), json!("PLAYER_ALLY"));
+    // not a number, even though it starts with a digit
+    assert_eq!(any("9.1.5"), json!("9.1.5"));
+}
+
+#[test]
+fn lists() {
+    assert_eq!(any("[]"), json!([]));
+    assert_eq!(any("[1,2,3]"), json!([1, 2, 3]));
+    assert_eq!(any(r#"[[HEAD,94779,T],[],["a, b",*]]"#), json!([["HEAD", 94779, true], [], ["a, b", null]]));
+}
+
+#[test]
+fn only_numeric_pairs_are_split() {
+    assert_eq!(any("22738/22738"), json!([22738, 22738]));
+    assert_eq!(any("0.5/-1"), json!([0.5, -1]));
+
+    assert_eq!(any("a/b"), json!("a/b"));
+    assert_eq!(any("elsweyr/sunspire_base"), json!("elsweyr/sunspire_base"));
+    assert_eq!(any("1/b"), json!("1/b"));
+    assert_eq!(any("1/2/3"), json!("1/2/3"));
+    assert_eq!(any(r#""1/2""#), json!("1/2"));
+}
+
+#[test]
+fn any_reads_single_field() {
+    let mut deserializer = Deserializer::new("T,12,a/b");
+
+    assert_eq!(Value::deserialize(&mut deserializer).unwrap(), json!(true));
+    assert_eq!(deserializer.remaining(), "12,a/b");
+    assert_eq!(Value::deserialize(&mut deserializer).unwrap(), json!(12));
+    assert_eq!(Value::deserialize(&mut deserializer).unwrap(), json!("a/b"));
+    assert!(deserializer.is_depleted());
+}
+
+#[test]
+fn whole_line_is_deserialized_explicitly() {
+    let line = r#"11,COMBAT_EVENT,T,"Some, name",[1,[2,3]],22738/22738,*,0.5"#;
+    let mut deserializer = Deserializer::new(line);
+
+    let fields = deserializer.deserialize_line(Fields).unwrap();
+    assert_eq!(fields, [
+        json!(11),
+        json!("COMBAT_EVENT"),
+        json!(true),
+        json!("Some, name"),
+        json!([1, [2, 3]]),
+        json!([22738, 22738]),
+        Value::Null,
+        json!(0.5),
+    ]);
+    assert!(deserializer.is_depleted());
+}
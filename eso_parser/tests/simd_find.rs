@@ -0,0 +1,97 @@
+use eso_parser::{simd_find::SimdLevel, UNGUARDED_PADDING};
+
+fn naive(head: usize, s: &[u8], f: u8) -> usize {
+    s[head..].iter()
+        .position(|&c| c == f)
+        .map_or(s.len(), |i| head + i)
+}
+
+/// buffers of every length up to a few blocks of the widest level,
+/// with delimiter at every position (or missing), and delimiters placed in the padding
+fn cases() -> impl Iterator<Item = (Vec<u8>, usize)> {
+    (0..200).flat_map(|len| {
+        (0..=len).map(move |delimiter| {
+            let mut buf = vec![b'a'; len + UNGUARDED_PADDING];
+            if delimiter < len {
+                buf[delimiter] = b',';
+            }
+            // must not be found, as it's past the end
+            buf[len..].fill(b',');
+
+            (buf, len)
+        })
+    })
+}
+
+#[test]
+fn every_level_matches_naive_search() {
+    for &level in SimdLevel::ALL.iter().filter(|level| level.is_supported()) {
+        for (buf, len) in cases() {
+            let s = &buf[..len];
+
+            for head in [0, 1, 7, 16, 33, 64].into_iter().filter(|&head| head <= len) {
+                let expected = naive(head, s, b',');
+
+                assert_eq!(level.find(head, s, b','), expected, "{level:?} len {len} head {head}");
+                // SAFETY: `buf` holds `UNGUARDED_PADDING` bytes past `s`
+                let unguarded = unsafe { level.find_unguarded(head, s, b',') };
+                assert_eq!(unguarded, expected, "{level:?} unguarded len {len} head {head}");
+            }
+        }
+    }
+}
+
+#[test]
+fn detected_level_is_supported() {
+    assert!(SimdLevel::detect().is_supported());
+    assert!(SimdLevel::Scalar.is_supported());
+}
+
+#[cfg(all(feature = "nightly", target_arch = "x86_64"))]
+#[test]
+fn avx512_matches_scalar_across_blocks() {
+    if !SimdLevel::Avx512.is_supported() {
+        return;
+    }
+
+    // delimiters around the ends of the first two 64 byte blocks
+    for len in [63, 64, 65, 127, 128, 129, 200] {
+        for delimiter in [0, 62, 63, 64, 65, 126, 127, 128, 129, len] {
+            let mut buf = vec![b'a'; len + UNGUARDED_PADDING];
+            if delimiter < len {
+                buf[delimiter] = b',';
+            }
+            buf[len..].fill(b',');
+            let s = &buf[..len];
+
+            for head in [0, 1, 63, 64, 65].into_iter().filter(|&head| head <= len) {
+                let expected = SimdLevel::Scalar.find(head, s, b',');
+
+                assert_eq!(SimdLevel::Avx512.find(head, s, b','), expected, "len {len} delimiter {delimiter} head {head}");
+                // SAFETY: `buf` holds `UNGUARDED_PADDING` bytes past `s`
+                let unguarded = unsafe { SimdLevel::Avx512.find_unguarded(head, s, b',') };
+                assert_eq!(unguarded, expected, "unguarded len {len} delimiter {delimiter} head {head}");
+            }
+        }
+    }
+}
+
+#[test]
+fn readers_split_tokens_of_every_length() {
+    use eso_parser::{EsoLogReader, EsoReaderTrait, UnguardedEsoLogReader};
+
+    let tokens: Vec<String> = (1..150)
+        .map(|len| "x".repeat(len))
+        .collect();
+    let line = tokens.join(",");
+
+    let mut buf = line.clone();
+    buf.push_str(&",".repeat(UNGUARDED_PADDING));
+    let padded = &buf[..line.len()];
+
+    let guarded: Vec<_> = EsoLogReader::read_line(&line).collect();
+    let unguarded: Vec<_> = UnguardedEsoLogReader::read_line(padded).collect();
+
+    assert_eq!(guarded, tokens);
+    assert_eq!(unguarded, tokens);
+}